@@ -0,0 +1,93 @@
+//! Compares the regex driven passes that used to split a RainDocument text against the single
+//! pass lexer, and times RainDocument parsing over growing texts to check it stays linear.
+//! Run with `cargo bench -p dotrain --bench parse`.
+
+use std::time::{Duration, Instant};
+use dotrain::{
+    __bench::split_dotrain,
+    exclusive_parse, fill_in, inclusive_parse, RainDocument,
+    types::patterns::{BINDING_PATTERN, COMMENT_PATTERN, IMPORTS_PATTERN, WS_PATTERN},
+};
+
+const ITERATIONS: u32 = 20;
+
+/// the comments, imports, bindings and remaining text passes over a RainDocument text
+fn regex_passes(text: &str, body_start: usize) -> usize {
+    let mut document = text.to_owned();
+    fill_in(&mut document, [0, body_start]).unwrap();
+    let mut count = 0;
+    for comment in inclusive_parse(&document, &COMMENT_PATTERN, 0) {
+        fill_in(&mut document, comment.1).unwrap();
+        count += 1;
+    }
+    for mut statement in exclusive_parse(&document, &IMPORTS_PATTERN, 0, true)
+        .into_iter()
+        .skip(1)
+    {
+        if let Some(index) = statement.0.find('#') {
+            statement.1[1] = statement.1[0] + index;
+        }
+        fill_in(&mut document, [statement.1[0] - 1, statement.1[1]]).unwrap();
+        count += 1;
+    }
+    for binding in exclusive_parse(&document, &BINDING_PATTERN, 0, true)
+        .into_iter()
+        .skip(1)
+    {
+        fill_in(&mut document, [binding.1[0] - 1, binding.1[1]]).unwrap();
+        count += 1;
+    }
+    count + exclusive_parse(&document, &WS_PATTERN, 0, false).len()
+}
+
+fn lexer_passes(text: &str, body_start: usize) -> usize {
+    let sections = split_dotrain(text, body_start);
+    sections.comments.len()
+        + sections.imports.len()
+        + sections.bindings.len()
+        + sections.unexpected.len()
+}
+
+/// builds a dotrain text with the given number of bindings
+fn dotrain_text(bindings: usize) -> String {
+    let mut text = "some-front-matter: 1\n---\n".to_owned();
+    for i in 0..bindings {
+        text.push_str(&format!(
+            "/* binding {i} */\n#binding-{i}\n_: add(1 2),\n_ _: binding-{i} 3;\n\n"
+        ));
+    }
+    text
+}
+
+fn time(f: impl Fn()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>14} {:>14} {:>14}",
+        "bindings", "regex", "lexer", "parse"
+    );
+    for bindings in [100, 200, 400, 800, 1600] {
+        let text = dotrain_text(bindings);
+        let body_start = text.find("---").unwrap() + 3;
+        assert_eq!(
+            regex_passes(&text, body_start),
+            lexer_passes(&text, body_start)
+        );
+        let regex = time(|| {
+            regex_passes(&text, body_start);
+        });
+        let lexer = time(|| {
+            lexer_passes(&text, body_start);
+        });
+        let parse = time(|| {
+            RainDocument::create(text.clone(), None, None, None);
+        });
+        println!("{bindings:>10} {regex:>14.2?} {lexer:>14.2?} {parse:>14.2?}");
+    }
+}
@@ -1,16 +1,27 @@
 use rain_metadata::Store;
 use std::{
-    fs::read_to_string,
+    fs::{read_to_string, write},
     sync::{Arc, RwLock},
 };
-use super::{rainconfig::RainConfigStruct, super::parser::raindocument::RainDocument, Compose};
+use super::{
+    rainconfig::RainConfigStruct,
+    super::{
+        parser::{raindocument::RainDocument, parse_rebind_matrix},
+        composer::ComposeOptions,
+    },
+    Compose,
+};
 
 /// Composes only the given .rain files based on provided options
 pub async fn compose_target(opts: Compose) -> anyhow::Result<String> {
     let force = opts.force.unwrap_or(false);
     let local_data_only = opts.local_data_only.unwrap_or(false);
-    let store = if let Some(rainconfig_path) = &opts.config {
-        let rainconfig = RainConfigStruct::read(rainconfig_path)?;
+    let rainconfig = opts
+        .config
+        .as_ref()
+        .map(RainConfigStruct::read)
+        .transpose()?;
+    let store = if let Some(rainconfig) = &rainconfig {
         if force {
             rainconfig.force_build_store()?
         } else {
@@ -25,16 +36,66 @@ pub async fn compose_target(opts: Compose) -> anyhow::Result<String> {
 
     // instantiate the RainDocument
     let mut rain_document = RainDocument::new(text, Some(store.clone()), 0, None);
+    if let Some(rainconfig) = rainconfig {
+        rain_document.set_front_matter_schema(rainconfig.front_matter_schema);
+        rain_document.set_lint_config(rainconfig.lints.unwrap_or_default());
+        rain_document.set_word_specs(rainconfig.word_specs.unwrap_or_default());
+    }
 
     // parse with overrides and exit in case overrides had errors
     rain_document.parse(!local_data_only, opts.bind).await;
 
-    // generate rainlang
+    // generate rainlang, use the entrypoints declared by the dotrain if none were given
+    let declared_entrypoints = if opts.entrypoint.is_empty() {
+        rain_document.declared_entrypoints()
+    } else {
+        vec![]
+    };
     let entrypoints = opts
         .entrypoint
         .iter()
+        .chain(declared_entrypoints.iter())
         .map(|e| e.as_str())
         .collect::<Vec<&str>>();
 
-    Ok(rain_document.compose(&entrypoints)?)
+    let default_options = ComposeOptions::default();
+    let options = ComposeOptions {
+        name_comments: opts.name_comments.unwrap_or(default_options.name_comments),
+        strip_comments: opts
+            .strip_comments
+            .unwrap_or(default_options.strip_comments),
+        minify: opts.minify.unwrap_or(default_options.minify),
+        header: opts.header.unwrap_or(default_options.header),
+    };
+
+    // compose once per variant of the rebind matrix if it was given
+    if let Some(matrix_path) = &opts.bind_matrix {
+        let variants = parse_rebind_matrix(&read_to_string(matrix_path)?)
+            .map_err(|e| anyhow::anyhow!("invalid rebind matrix: {}", e))?;
+        let texts = rain_document
+            .compose_variants(&entrypoints, variants, Some(options))
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(serde_json::to_string_pretty(&texts)?);
+    }
+
+    // write the sourcemap alongside if it was asked for
+    let text = if let Some(sourcemap_path) = &opts.sourcemap {
+        let composed = rain_document.compose_with_sourcemap(&entrypoints, Some(options))?;
+        let source_map =
+            rain_document.build_source_map(&composed, None, &opts.input.to_string_lossy());
+        write(sourcemap_path, serde_json::to_string(&source_map)?)?;
+        composed.text
+    } else {
+        rain_document.compose(&entrypoints, Some(options))?
+    };
+
+    // write the rain meta document and return its hash instead if it was asked for
+    if let Some(meta_path) = &opts.emit_meta {
+        let meta = rain_document.pack_meta(&text)?;
+        write(meta_path, meta.bytes)?;
+        Ok(meta.hash)
+    } else {
+        Ok(text)
+    }
 }
@@ -0,0 +1,26 @@
+use std::fs::{read_to_string, write};
+use super::{super::formatter::format_dotrain, Fmt};
+
+/// Formats the given .rain files in place, or only checks if they are formatted
+pub fn fmt_target(opts: Fmt) -> anyhow::Result<()> {
+    let mut unformatted = vec![];
+    for path in &opts.input {
+        let text = read_to_string(path)?;
+        let formatted = format_dotrain(&text);
+        if formatted != text {
+            if opts.check {
+                unformatted.push(path.display().to_string());
+            } else {
+                write(path, formatted)?;
+            }
+        }
+    }
+    if unformatted.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "following files are not formatted:\n{}",
+            unformatted.join("\n")
+        ))
+    }
+}
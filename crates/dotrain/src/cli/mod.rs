@@ -5,13 +5,15 @@
 //! and options while underlying functions handle each scenario
 
 use std::path::PathBuf;
-use clap::{Parser, Subcommand, command};
+use clap::{Parser, Subcommand};
 use crate::parser::{Rebind, parse_cli_key_val};
 
 mod compose;
+mod fmt;
 mod rainconfig;
 
 pub use compose::*;
+pub use fmt::*;
 pub use rainconfig::*;
 
 /// CLI app entrypoint sruct
@@ -26,6 +28,8 @@ struct Cli {
 pub enum Dotrain {
     /// Compose a .rain file to rainlang
     Compose(Compose),
+    /// Format .rain files in place
+    Fmt(Fmt),
     /// Prints 'rainconfig' info and description
     #[command(subcommand)]
     Rainconfig(RainconfigInfo),
@@ -37,12 +41,17 @@ pub struct Compose {
     /// Input .rain file path
     #[arg(short, long)]
     input: PathBuf,
-    /// Entrypoints
+    /// Entrypoints, if omitted the entrypoints declared by the .rain file's front matter
+    /// 'entrypoints' key or '@entrypoint' binding annotations are used
     #[arg(short, long)]
     entrypoint: Vec<String>,
     /// rebinds items with new literal values
     #[arg(short, long, value_parser = parse_cli_key_val)]
     bind: Option<Vec<Rebind>>,
+    /// Path to a json (array of key/value objects) or csv (header row of keys) matrix of rebinds,
+    /// composes once per row and prints a json array of the composed rainlang texts
+    #[arg(long, conflicts_with_all = ["sourcemap", "emit_meta"])]
+    bind_matrix: Option<PathBuf>,
     /// Path to the rainconfig json file that contains configurations,
     /// if provided will be used to when composing the .rain, see
     /// './example.rainconfig.json' for more details.
@@ -54,6 +63,38 @@ pub struct Compose {
     /// Only use local dotrain meta specified in rainconfig include field and dont search for them in subgraphs
     #[arg(short, long)]
     local_data_only: Option<bool>,
+    /// Path to write the Source Map (revision 3) json of the composed rainlang to
+    #[arg(long)]
+    sourcemap: Option<PathBuf>,
+    /// Prepend '/* index. name */' comment to each composed source, enabled by default
+    #[arg(long)]
+    name_comments: Option<bool>,
+    /// Remove the original comments of the composed sources
+    #[arg(long)]
+    strip_comments: Option<bool>,
+    /// Minify the whitespaces of the composed sources
+    #[arg(short, long)]
+    minify: Option<bool>,
+    /// Prepend a header comment containing the hash of the .rain file
+    #[arg(long)]
+    header: Option<bool>,
+    /// Path to write the cbor encoded Rain meta document of the .rain file and its composed
+    /// rainlang to, prints the meta hash instead of the composed rainlang, cannot be used with
+    /// '--sourcemap' as there is no Rain meta magic to pack a sourcemap with
+    #[arg(long, conflicts_with = "sourcemap")]
+    emit_meta: Option<PathBuf>,
+}
+
+/// Fmt subcommand entry point
+#[derive(Parser, Debug)]
+pub struct Fmt {
+    /// Input .rain file paths
+    #[arg(short, long, required = true)]
+    input: Vec<PathBuf>,
+    /// Only check if the files are formatted and exit with an error listing the
+    /// ones that are not, instead of writing them
+    #[arg(long)]
+    check: bool,
 }
 
 /// rainconfig available commands
@@ -67,6 +108,12 @@ pub enum RainconfigInfo {
     Include,
     /// Prints info about 'subgraphs' field
     Subgraphs,
+    /// Prints info about 'frontMatterSchema' field
+    FrontMatterSchema,
+    /// Prints info about 'lints' field
+    Lints,
+    /// Prints info about 'wordSpecs' field
+    WordSpecs,
 }
 
 /// Dispatches the CLI call based on the given options and commands
@@ -75,10 +122,21 @@ pub async fn dispatch(dotrain: Dotrain) -> anyhow::Result<()> {
         Dotrain::Compose(cli) => {
             println!("{}", compose_target(cli).await?);
         }
+        Dotrain::Fmt(cli) => fmt_target(cli)?,
         Dotrain::Rainconfig(v) => match v {
             RainconfigInfo::Info => println!("{}", rainconfig::RAINCONFIG_DESCRIPTION),
             RainconfigInfo::PrintAll => {
-                println!("{}", ["- include", "- subgraphs"].join("\n"))
+                println!(
+                    "{}",
+                    [
+                        "- include",
+                        "- subgraphs",
+                        "- frontMatterSchema",
+                        "- lints",
+                        "- wordSpecs"
+                    ]
+                    .join("\n")
+                )
             }
             RainconfigInfo::Include => {
                 println!("{}", rainconfig::RAINCONFIG_INCLUDE_DESCRIPTION)
@@ -86,6 +144,15 @@ pub async fn dispatch(dotrain: Dotrain) -> anyhow::Result<()> {
             RainconfigInfo::Subgraphs => {
                 println!("{}", rainconfig::RAINCONFIG_SUBGRAPHS_DESCRIPTION)
             }
+            RainconfigInfo::FrontMatterSchema => {
+                println!("{}", rainconfig::RAINCONFIG_FRONT_MATTER_SCHEMA_DESCRIPTION)
+            }
+            RainconfigInfo::Lints => {
+                println!("{}", rainconfig::RAINCONFIG_LINTS_DESCRIPTION)
+            }
+            RainconfigInfo::WordSpecs => {
+                println!("{}", rainconfig::RAINCONFIG_WORD_SPECS_DESCRIPTION)
+            }
         },
     };
     Ok(())
@@ -1,5 +1,6 @@
 use rain_metadata::Store;
 use serde::{Serialize, Deserialize};
+use crate::{parser::FrontMatterSchema, linter::LintConfig, types::words::WordSpecs};
 use std::{
     path::PathBuf,
     sync::{Arc, RwLock},
@@ -23,15 +24,33 @@ all fields in the rainconfig are optional and are as follows:
 
   - subgraphs: Additional subgraph endpoint URLs to include when searching for metas of 
   specified meta hashes in a rainlang document.
+
+  - frontMatterSchema: A JSON schema that the front matter (YAML or JSON) of the composing 
+  .rain file is validated against, syntax errors and schema violations are reported as problems.
+
+  - lints: Levels of the lints (allow, warn or deny) run over the composing .rain file keyed by 
+  their names, warned lints do not fail the compose while denied ones do.
+
+  - wordSpecs: Stack inputs and outputs and operand args of words keyed by the word, used for 
+  checking the inputs and operand args of opcodes and the outputs of lines of the composing 
+  .rain file.
 ";
 pub(crate) const RAINCONFIG_INCLUDE_DESCRIPTION: &str = r"Specifies a list of directories (files/folders) to be included and watched. folders will be watched recursively for .rain files. These files will be available as dotrain meta in the cas so if their hash is specified in a compilation target they will get resolved.";
 pub(crate) const RAINCONFIG_SUBGRAPHS_DESCRIPTION: &str = r"Additional subgraph endpoint URLs to include when searching for metas of specified meta hashes in a rainlang document.";
+pub(crate) const RAINCONFIG_FRONT_MATTER_SCHEMA_DESCRIPTION: &str = r"A JSON schema that the front matter (YAML or JSON) of the composing .rain file is validated against, syntax errors and schema violations are reported as problems. supports 'type', 'properties', 'required', 'additionalProperties', 'items', 'enum', 'pattern', 'minimum' and 'maximum' keywords.";
+pub(crate) const RAINCONFIG_LINTS_DESCRIPTION: &str = r"Levels of the lints (allow, warn or deny) run over the composing .rain file keyed by their names, warned lints do not fail the compose while denied ones do. available lints are 'unused-binding', 'unused-import', 'unused-alias' (allowed by default) and 'shadowed-name', their levels can also be set in comments of the .rain file with '@allow', '@warn' and '@deny' annotations followed by the lint names.";
+
+pub(crate) const RAINCONFIG_WORD_SPECS_DESCRIPTION: &str = r"Stack inputs and outputs and operand args of words keyed by the word, used for checking the inputs and operand args of opcodes and the outputs of lines of the composing .rain file. each word spec has 'inputs' and 'outputs' counts, an optional 'maxInputs' for words with variable inputs, in which case 'inputs' is the minimum, and optional 'operandArgs' which is the list of the word's operand args each with a 'name' and optional 'description', 'kind' ('literal' or 'quote'), 'min' and 'max' values and 'optional' flag, words without a spec are not checked.";
 
 /// Data structure of deserialized rainconfig.json
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct RainConfigStruct {
     pub include: Option<Vec<PathBuf>>,
     pub subgraphs: Option<Vec<String>>,
+    pub front_matter_schema: Option<FrontMatterSchema>,
+    pub lints: Option<LintConfig>,
+    pub word_specs: Option<WordSpecs>,
 }
 
 impl RainConfigStruct {
@@ -0,0 +1,142 @@
+use serde::{Serialize, Deserialize};
+use rain_metadata::{KnownMagic, RainMetaDocumentV1Item, ContentType, ContentEncoding, ContentLanguage};
+use super::{
+    ComposeOptions,
+    super::{error::ComposeError, parser::raindocument::RainDocument},
+};
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Type of a composition packed as a Rain meta document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ComposedMeta {
+    /// cbor encoded RainMetaDocumentV1 sequence
+    #[serde(with = "serde_bytes")]
+    #[cfg_attr(feature = "js-api", tsify(type = "Uint8Array"))]
+    pub bytes: Vec<u8>,
+    /// keccak256 hash of the bytes
+    pub hash: String,
+}
+
+impl RainDocument {
+    /// composes the specified entrypoints and packs this instance's text as `DotrainV1` item and
+    /// the composed rainlang as `RainlangSourceV1` item into a cbor encoded RainMetaDocumentV1 sequence,
+    /// the sourcemap of the composed rainlang is not packed as there is no known magic for it and
+    /// a meta document with an item of unknown magic cannot be decoded, so it would not be importable
+    pub fn compose_meta(
+        &self,
+        entrypoints: &[&str],
+        options: Option<ComposeOptions>,
+    ) -> Result<ComposedMeta, ComposeError> {
+        let composed = self.compose(entrypoints, options)?;
+        self.pack_meta(&composed)
+    }
+
+    /// packs this instance's text as `DotrainV1` item and the given composed rainlang of this
+    /// instance as `RainlangSourceV1` item into a cbor encoded RainMetaDocumentV1 sequence
+    pub(crate) fn pack_meta(&self, composed: &str) -> Result<ComposedMeta, ComposeError> {
+        let items = vec![
+            self.dotrain_meta_item(),
+            meta_item(
+                composed.as_bytes(),
+                KnownMagic::RainlangSourceV1,
+                ContentType::OctetStream,
+            ),
+        ];
+        let bytes = RainMetaDocumentV1Item::cbor_encode_seq(&items, KnownMagic::RainMetaDocumentV1)
+            .or(Err(ComposeError::Reject(
+                "could not encode rain meta document".to_owned(),
+            )))?;
+        let hash = alloy_primitives::hex::encode_prefixed(alloy_primitives::keccak256(&bytes));
+        Ok(ComposedMeta { bytes, hash })
+    }
+
+    /// This instance's text as `DotrainV1` meta item
+    pub(crate) fn dotrain_meta_item(&self) -> RainMetaDocumentV1Item {
+        meta_item(
+            self.text.as_bytes(),
+            KnownMagic::DotrainV1,
+            ContentType::OctetStream,
+        )
+    }
+
+    /// Hash of this instance's text as `DotrainV1` meta, same as it is when stored in a meta Store
+    pub(crate) fn dotrain_meta_hash(&self) -> Result<String, ComposeError> {
+        let meta_bytes = self
+            .dotrain_meta_item()
+            .cbor_encode()
+            .or(Err(ComposeError::Reject(
+                "could not encode dotrain meta".to_owned(),
+            )))?;
+        Ok(alloy_primitives::hex::encode_prefixed(
+            alloy_primitives::keccak256(meta_bytes),
+        ))
+    }
+}
+
+/// Builds an uncompressed meta item of the given payload
+fn meta_item(
+    payload: &[u8],
+    magic: KnownMagic,
+    content_type: ContentType,
+) -> RainMetaDocumentV1Item {
+    RainMetaDocumentV1Item {
+        payload: serde_bytes::ByteBuf::from(payload),
+        magic,
+        content_type,
+        content_encoding: ContentEncoding::None,
+        content_language: ContentLanguage::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, RwLock};
+
+    #[test]
+    fn test_compose_meta() {
+        let dotrain_text = r"---
+#main
+_: opcode-1(1 2);
+";
+        let rain_document = RainDocument::create(dotrain_text.to_owned(), None, None, None);
+        let result = rain_document.compose_meta(&["main"], None).unwrap();
+        assert_eq!(
+            result.hash,
+            alloy_primitives::hex::encode_prefixed(alloy_primitives::keccak256(&result.bytes))
+        );
+
+        let items = RainMetaDocumentV1Item::cbor_decode(&result.bytes).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].magic, KnownMagic::DotrainV1);
+        assert_eq!(
+            items[0].clone().unpack_into::<String>().unwrap(),
+            dotrain_text
+        );
+        assert_eq!(items[1].magic, KnownMagic::RainlangSourceV1);
+        assert_eq!(
+            items[1].clone().unpack_into::<String>().unwrap(),
+            rain_document.compose(&["main"], None).unwrap()
+        );
+
+        // the packed meta is consumable as an import of another dotrain
+        let mut store = rain_metadata::Store::new();
+        let hash_bytes = alloy_primitives::hex::decode(&result.hash).unwrap();
+        store.update_with(&hash_bytes, &result.bytes);
+        let importer = RainDocument::create(
+            format!(
+                "---\n@imp {}\n#exp\n_: opcode-2<'imp.main>(3 4);",
+                result.hash
+            ),
+            Some(Arc::new(RwLock::new(store))),
+            None,
+            None,
+        );
+        assert!(importer.all_problems().is_empty());
+        assert!(importer.compose(&["exp"], None).is_ok());
+    }
+}
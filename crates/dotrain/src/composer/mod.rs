@@ -1,4 +1,6 @@
-use rain_metadata::Store;
+use rain_metadata::{Store, types::authoring::v1::AuthoringMeta};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
 use std::{
     sync::{Arc, RwLock},
     collections::VecDeque,
@@ -6,18 +8,37 @@ use std::{
 use magic_string_rain::{MagicString, OverwriteOptions, GenerateDecodedMapOptions};
 use crate::types::ast::PragmaStatement;
 
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+mod meta;
+mod sourcemap;
+
+pub use meta::*;
+pub use sourcemap::*;
+
 use super::{
     error::{ComposeError, ErrorCode},
     parser::{RainlangDocument, RainDocument, exclusive_parse, Rebind},
     types::{
         patterns::{WORD_PATTERN, NAMESPACE_SEGMENT_PATTERN},
+        literal::string_literal_end,
         ast::{
-            Offsets, Problem, Node, Namespace, NamespaceItem, NamespaceLeaf, Binding, BindingItem,
-            Import,
+            Offsets, Problem, ProblemOrigin, Severity, Node, Namespace, NamespaceItem,
+            NamespaceLeaf, Binding, BindingItem, Import, Comment,
         },
     },
 };
 
+/// Maximum number of sources a composed rainlang can have
+pub const MAX_SOURCES: usize = u8::MAX as usize;
+
+/// Front matter key that lists the entrypoints of a dotrain
+pub const ENTRYPOINTS_KEY: &str = "entrypoints";
+
+/// Annotation that marks a binding as an entrypoint when it appears in a comment right before the binding
+pub const ENTRYPOINT_ANNOTATION: &str = "@entrypoint";
+
 /// a composing target element
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct ComposeTargetElement<'a> {
@@ -30,14 +51,15 @@ pub(crate) struct ComposeTargetElement<'a> {
     pub(crate) item: RainlangDocument,
 }
 
-/// a composing target
+/// a composing target, namespace is the last field so that the derived equality
+/// compares it only when all the other fields are already equal
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct ComposeTarget<'a> {
     pub(crate) namespace_path: String,
-    pub(crate) namespace: &'a Namespace,
     pub(crate) hash: &'a str,
     pub(crate) import_index: isize,
     pub(crate) element: ComposeTargetElement<'a>,
+    pub(crate) namespace: &'a Namespace,
 }
 
 impl<'a> ComposeTarget<'a> {
@@ -65,6 +87,71 @@ impl<'a> ComposeTarget<'a> {
         }
     }
 
+    /// builds the generator of this target's rainlang text with the given deps indexes
+    /// and prepends the name comment if an index is given for it
+    fn build_generator(
+        &self,
+        name_comment_index: Option<usize>,
+        deps: &mut VecDeque<usize>,
+        options: &ComposeOptions,
+    ) -> Result<MagicString, ComposeError> {
+        let mut generator = MagicString::new(self.element.content);
+
+        // sourcemap binding name comment
+        if let Some(index) = name_comment_index {
+            generator
+                .prepend(&self.build_name_comment(index))
+                .or(Err("could not build sourcemap".to_owned()))
+                .map_err(ComposeError::Reject)?;
+        }
+
+        // sourcemap original comments and whitespaces
+        if options.strip_comments || options.minify {
+            for (position, replacement) in trivia_edits(self.element.content, options) {
+                if replacement.is_empty() {
+                    generator.remove(position[0] as i64, position[1] as i64)
+                } else {
+                    generator.overwrite(
+                        position[0] as i64,
+                        position[1] as i64,
+                        &replacement,
+                        OverwriteOptions::default(),
+                    )
+                }
+                .or(Err("could not build sourcemap".to_owned()))
+                .map_err(ComposeError::Reject)?;
+            }
+        }
+
+        // sourcemap pragmas
+        for PragmaStatement { sources, .. } in &self.element.item.pragmas {
+            for (source, literal) in sources {
+                if let Some(v) = literal {
+                    generator
+                        .overwrite(
+                            source.1[0] as i64,
+                            source.1[1] as i64,
+                            v,
+                            OverwriteOptions::default(),
+                        )
+                        .or(Err("could not build sourcemap".to_owned()))
+                        .map_err(ComposeError::Reject)?;
+                }
+            }
+        }
+        build_sourcemap(
+            self.element
+                .item
+                .ast
+                .iter()
+                .flat_map(|src| src.lines.iter().flat_map(|line| &line.nodes)),
+            &mut generator,
+            deps,
+        )
+        .map_err(ComposeError::Reject)?;
+        Ok(generator)
+    }
+
     fn build_name_comment(&self, index: usize) -> String {
         let header = "/* ".to_string() + &index.to_string() + ". ";
         if self.namespace_path.is_empty() {
@@ -83,16 +170,338 @@ pub(crate) struct ComposeSourcemap<'a> {
     pub(crate) mappings: Vec<Vec<Vec<i64>>>,
 }
 
+impl ComposeSourcemap<'_> {
+    /// resolves the relative decoded mappings of this item into byte offsets mappings, the
+    /// given offset is the position of this item's generated string in the composed text
+    fn resolve_mappings(&self, offset: usize) -> Vec<ComposeMapping> {
+        let content = self.target.element.content;
+        let generated_lines = line_starts(&self.generated_string);
+        let original_lines = line_starts(content);
+
+        // absolute generated and original offsets of each mapping segment, generated column is
+        // relative to the previous segment of the same line while the rest are relative to the
+        // previous segment of the whole mappings
+        let mut segments: Vec<(usize, usize)> = vec![];
+        let mut original_line = 0;
+        let mut original_column = 0;
+        for (line_index, line) in self.mappings.iter().enumerate() {
+            let mut generated_column = 0;
+            for segment in line.iter().filter(|v| v.len() >= 4) {
+                generated_column += segment[0];
+                original_line += segment[2];
+                original_column += segment[3];
+                if let (Some(generated_line_start), Some(original_line_start)) = (
+                    generated_lines.get(line_index),
+                    original_lines.get(original_line as usize),
+                ) {
+                    segments.push((
+                        generated_line_start + generated_column as usize,
+                        original_line_start + original_column as usize,
+                    ));
+                }
+            }
+        }
+
+        let mut mappings: Vec<ComposeMapping> = vec![];
+
+        // anything before the first segment is the binding name comment
+        if let Some((first, _)) = segments.first() {
+            if *first > 0 {
+                mappings.push(ComposeMapping {
+                    generated: [offset, offset + first],
+                    original: self.target.element.name_position,
+                    hash: self.target.hash.to_owned(),
+                    import_index: self.target.import_index,
+                });
+            }
+        }
+
+        // each segment spans until the next one, consecutive unedited segments are merged into one
+        let content_offset = self.target.element.content_position[0];
+        for (index, (generated_start, original_start)) in segments.iter().enumerate() {
+            let (generated_end, original_end) = match segments.get(index + 1) {
+                Some((g, o)) if o > original_start => (*g, *o),
+                Some((g, _)) => (*g, original_start + g - generated_start),
+                None => (
+                    self.generated_string.len(),
+                    original_start + self.generated_string.len() - generated_start,
+                ),
+            };
+            let generated = [offset + generated_start, offset + generated_end];
+            let original = [
+                content_offset + original_start,
+                content_offset + original_end.min(content.len()),
+            ];
+            if let Some(last) = mappings.last_mut() {
+                if last.generated[1] == generated[0]
+                    && last.original[1] == original[0]
+                    && last.generated[1] - last.generated[0] == last.original[1] - last.original[0]
+                    && generated[1] - generated[0] == original[1] - original[0]
+                {
+                    last.generated[1] = generated[1];
+                    last.original[1] = original[1];
+                    continue;
+                }
+            }
+            mappings.push(ComposeMapping {
+                generated,
+                original,
+                hash: self.target.hash.to_owned(),
+                import_index: self.target.import_index,
+            });
+        }
+        mappings
+    }
+}
+
+/// Type of a fragment of a composed rainlang text mapped back to its original offsets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ComposeMapping {
+    /// offsets of the fragment in the composed rainlang text
+    pub generated: Offsets,
+    /// offsets of the fragment in the text of the dotrain it originates from
+    pub original: Offsets,
+    /// hash of the imported dotrain the fragment originates from, empty if it is from the root dotrain
+    pub hash: String,
+    /// index of the root dotrain's import the fragment was resolved through, -1 if it is from the root dotrain
+    pub import_index: isize,
+}
+
+/// Type of a composed rainlang text with its sourcemap
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ComposedRainlang {
+    /// the composed rainlang text
+    pub text: String,
+    /// mappings of the composed text fragments in order of their generated offsets
+    pub mappings: Vec<ComposeMapping>,
+}
+
+/// Options for composing a RainDocument into rainlang
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ComposeOptions {
+    /// prepends `/* index. path.name */` comment to each source, enabled by default
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub name_comments: bool,
+    /// removes the original comments of each source
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub strip_comments: bool,
+    /// minifies the whitespaces of each source
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub minify: bool,
+    /// prepends a header comment containing the hash of the root dotrain
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub header: bool,
+}
+
+impl Default for ComposeOptions {
+    fn default() -> Self {
+        ComposeOptions {
+            name_comments: true,
+            strip_comments: false,
+            minify: false,
+            header: false,
+        }
+    }
+}
+
+impl ComposeOptions {
+    /// options that produce the least bytes of rainlang text
+    pub fn minimal() -> Self {
+        ComposeOptions {
+            name_comments: false,
+            strip_comments: true,
+            minify: true,
+            header: false,
+        }
+    }
+}
+
+/// Type of a composed source of a rainlang expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ComposedSource {
+    /// index of this source in the composed expression
+    pub index: usize,
+    /// namespace path of the binding this source is composed from, empty if it is at root namespace
+    pub namespace_path: String,
+    /// name of the binding this source is composed from
+    pub name: String,
+    /// hash of the imported dotrain the binding originates from, empty if it is from the root dotrain
+    pub hash: String,
+    /// literal values of this source's pragma statements
+    pub pragmas: Vec<String>,
+    /// generated rainlang text of this source
+    pub text: String,
+}
+
+/// Type of a composed rainlang expression, sources are in order of their index where
+/// the entrypoints come first followed by their dependencies
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ComposedExpression {
+    /// header comment containing the hash of the root dotrain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub header: Option<String>,
+    /// composed sources of the expression in order of their index
+    pub sources: Vec<ComposedSource>,
+}
+
+impl ComposedExpression {
+    /// produces the rainlang text of this composed expression
+    pub fn to_rainlang(&self) -> String {
+        self.header.clone().unwrap_or_default()
+            + &self
+                .sources
+                .iter()
+                .map(|s| s.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n")
+    }
+}
+
+impl ComposedRainlang {
+    /// finds the mapping of the fragment that the given offset of the composed text falls into
+    pub fn lookup(&self, offset: usize) -> Option<&ComposeMapping> {
+        self.mappings
+            .iter()
+            .find(|v| v.generated[0] <= offset && offset < v.generated[1])
+    }
+}
+
 impl RainDocument {
     /// composes to rainlang text from the specified entrypoints
-    pub fn compose(&self, entrypoints: &[&str]) -> Result<String, ComposeError> {
-        let sourcemaps = self.build_targets_sourcemap(entrypoints)?;
-        let rainlang_string = sourcemaps
-            .iter()
-            .map(|s| s.generated_string.as_str())
-            .collect::<Vec<_>>()
-            .join("\n\n");
-        Ok(rainlang_string)
+    pub fn compose(
+        &self,
+        entrypoints: &[&str],
+        options: Option<ComposeOptions>,
+    ) -> Result<String, ComposeError> {
+        Ok(self.compose_expression(entrypoints, options)?.to_rainlang())
+    }
+
+    /// composes the specified entrypoints into a structured rainlang expression
+    pub fn compose_expression(
+        &self,
+        entrypoints: &[&str],
+        options: Option<ComposeOptions>,
+    ) -> Result<ComposedExpression, ComposeError> {
+        let options = options.unwrap_or_default();
+        let sourcemaps = self.build_targets_sourcemap(entrypoints, &options)?;
+        let sources = sourcemaps
+            .into_iter()
+            .enumerate()
+            .map(|(index, s)| ComposedSource {
+                index,
+                namespace_path: s.target.namespace_path,
+                name: s.target.element.name.to_owned(),
+                hash: s.target.hash.to_owned(),
+                pragmas: s
+                    .target
+                    .element
+                    .item
+                    .pragmas
+                    .iter()
+                    .flat_map(|p| &p.sources)
+                    .map(|(item, literal)| literal.as_ref().unwrap_or(&item.0).clone())
+                    .collect(),
+                text: s.generated_string,
+            })
+            .collect();
+        Ok(ComposedExpression {
+            header: self.build_header(&options)?,
+            sources,
+        })
+    }
+
+    /// composes to rainlang text from the specified entrypoints along with the mappings of
+    /// the composed text fragments back to their original offsets
+    pub fn compose_with_sourcemap(
+        &self,
+        entrypoints: &[&str],
+        options: Option<ComposeOptions>,
+    ) -> Result<ComposedRainlang, ComposeError> {
+        let options = options.unwrap_or_default();
+        let sourcemaps = self.build_targets_sourcemap(entrypoints, &options)?;
+        let mut text = self.build_header(&options)?.unwrap_or_default();
+        let mut mappings = vec![];
+        for (index, sourcemap) in sourcemaps.iter().enumerate() {
+            if index > 0 {
+                text.push_str("\n\n");
+            }
+            mappings.extend(sourcemap.resolve_mappings(text.len()));
+            text.push_str(&sourcemap.generated_string);
+        }
+        Ok(ComposedRainlang { text, mappings })
+    }
+
+    /// entrypoints declared by this instance, first the ones listed under the `entrypoints`
+    /// key of the front matter and then the bindings annotated with `@entrypoint` comment
+    pub fn declared_entrypoints(&self) -> Vec<String> {
+        let mut entrypoints = front_matter_entrypoints(self.front_matter_value());
+        for binding in &self.bindings {
+            let is_annotated = self.comments.iter().any(|comment| {
+                self.is_binding_annotation(comment, binding)
+                    && comment
+                        .comment
+                        .trim_start_matches("/*")
+                        .trim_end_matches("*/")
+                        .split_whitespace()
+                        .any(|v| v == ENTRYPOINT_ANNOTATION)
+            });
+            if is_annotated && !entrypoints.contains(&binding.name) {
+                entrypoints.push(binding.name.clone());
+            }
+        }
+        entrypoints
+    }
+
+    /// checks if the given comment is right before the given binding, i.e. there is
+    /// only `#` between them, which makes its annotations apply to that binding
+    pub(crate) fn is_binding_annotation(&self, comment: &Comment, binding: &Binding) -> bool {
+        comment.position[1] <= binding.name_position[0]
+            && self.text[comment.position[1]..binding.name_position[0]].trim() == "#"
+    }
+
+    /// composes to rainlang text from the entrypoints declared by this instance
+    pub fn compose_declared(
+        &self,
+        options: Option<ComposeOptions>,
+    ) -> Result<String, ComposeError> {
+        let entrypoints = self.declared_entrypoints();
+        if entrypoints.is_empty() {
+            return Err(ComposeError::Reject(
+                "no declared entrypoints found in front matter or binding annotations".to_owned(),
+            ));
+        }
+        self.compose(
+            &entrypoints
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<&str>>(),
+            options,
+        )
+    }
+
+    /// composes the specified entrypoints once per each of the given rebind variants, the variants
+    /// are applied to copies of this instance's namespace so the text is parsed only once
+    pub fn compose_variants(
+        &self,
+        entrypoints: &[&str],
+        variants: Vec<Vec<Rebind>>,
+        options: Option<ComposeOptions>,
+    ) -> Vec<Result<String, ComposeError>> {
+        variants
+            .into_iter()
+            .map(|rebinds| self.with_rebinds(rebinds).compose(entrypoints, options))
+            .collect()
     }
 
     /// composes a given text as RainDocument into rainlang with remote meta search disabled for parsing
@@ -101,8 +510,10 @@ impl RainDocument {
         entrypoints: &[&str],
         meta_store: Option<Arc<RwLock<Store>>>,
         rebinds: Option<Vec<Rebind>>,
+        options: Option<ComposeOptions>,
     ) -> Result<String, ComposeError> {
-        RainDocument::create(text.to_string(), meta_store, None, rebinds).compose(entrypoints)
+        RainDocument::create(text.to_string(), meta_store, None, rebinds)
+            .compose(entrypoints, options)
     }
 
     /// composes a given text as RainDocument into rainlang with remote meta search enabled for parsing
@@ -111,10 +522,11 @@ impl RainDocument {
         entrypoints: &[&str],
         meta_store: Option<Arc<RwLock<Store>>>,
         rebinds: Option<Vec<Rebind>>,
+        options: Option<ComposeOptions>,
     ) -> Result<String, ComposeError> {
         RainDocument::create_async(text.to_string(), meta_store, None, rebinds)
             .await
-            .compose(entrypoints)
+            .compose(entrypoints, options)
     }
 }
 
@@ -123,12 +535,14 @@ impl RainDocument {
     pub(crate) fn build_targets_sourcemap(
         &self,
         entrypoints: &[&str],
-    ) -> Result<Vec<ComposeSourcemap>, ComposeError> {
+        options: &ComposeOptions,
+    ) -> Result<Vec<ComposeSourcemap<'_>>, ComposeError> {
         if entrypoints.is_empty() {
             return Err(ComposeError::Reject("no entrypoints specified".to_owned()));
         }
-        if !self.problems.is_empty() {
-            return Err(ComposeError::Problems(self.problems.clone()));
+        let errors = error_problems(&self.problems);
+        if !errors.is_empty() {
+            return Err(ComposeError::Problems(errors));
         }
 
         let mut nodes: Vec<ComposeTarget> = vec![];
@@ -137,20 +551,29 @@ impl RainDocument {
         for entrypoint in entrypoints {
             match search_namespace(entrypoint, &self.namespace, "") {
                 Ok((parent_namespace, leaf, binding, namespace_path)) => {
-                    if !binding.problems.is_empty() {
-                        return Err(ComposeError::from_problems(
-                            &binding.problems,
+                    let errors = error_problems(&binding.problems);
+                    if !errors.is_empty() {
+                        return Err(self.compose_problems(
+                            &errors,
+                            0,
                             leaf.import_index,
-                            &self.imports,
+                            &leaf.hash,
                         ));
                     }
-                    let rainlang_doc =
-                        RainlangDocument::create(binding.content.clone(), parent_namespace, None);
-                    if !rainlang_doc.problems.is_empty() {
-                        return Err(ComposeError::from_problems(
-                            &rainlang_doc.problems,
+                    let mut rainlang_doc = RainlangDocument::create(
+                        binding.content.clone(),
+                        parent_namespace,
+                        self.import_words(&leaf.hash),
+                        Some(&self.meta_store),
+                    );
+                    rainlang_doc.check_word_specs(&self.word_specs);
+                    let errors = error_problems(&rainlang_doc.problems);
+                    if !errors.is_empty() {
+                        return Err(self.compose_problems(
+                            &errors,
+                            binding.content_position[0],
                             leaf.import_index,
-                            &self.imports,
+                            &leaf.hash,
                         ));
                     }
                     nodes.push(ComposeTarget::create(
@@ -169,24 +592,73 @@ impl RainDocument {
 
         // resolve deps of deps recursively and return the array of deps indexes that
         // will be used to replace with dep identifiers in the text
-        let mut deps_indexes = self.resolve_deps(&mut nodes)?;
+        let deps_indexes = self.resolve_deps(&mut nodes)?;
 
         // validate each node dep path
         for (index, _) in deps_indexes.iter().enumerate() {
-            let chain = vec![index as u8];
+            let chain = vec![index];
             validate_dep_path(index, &deps_indexes, &chain).map_err(|_| {
-                if nodes[index].import_index == -1 {
-                    ComposeError::Problems(vec![ErrorCode::CircularDependency
-                        .to_problem(vec![], nodes[index].element.name_position)])
-                } else {
-                    ComposeError::Problems(vec![ErrorCode::CircularDependency.to_problem(
-                        vec![],
-                        self.imports[nodes[index].import_index as usize].hash_position,
-                    )])
-                }
+                self.compose_problems(
+                    &[ErrorCode::CircularDependency
+                        .to_problem(vec![], nodes[index].element.name_position)],
+                    0,
+                    nodes[index].import_index,
+                    nodes[index].hash,
+                )
             })?;
         }
 
+        // collapse each dependency that generates byte identical rainlang as a previous
+        // source into that source, entrypoints always keep their index, this is repeated
+        // until no more changes as renumbering deps can make more sources identical
+        let mut representatives: Vec<usize> = (0..nodes.len()).collect();
+        let mut changed = nodes.len() > entrypoints.len();
+        while changed {
+            changed = false;
+            let mut generated: Vec<Option<String>> = vec![];
+            for (index, node) in nodes.iter().enumerate() {
+                if representatives[index] != index {
+                    generated.push(None);
+                    continue;
+                }
+                let mut deps = deps_indexes[index]
+                    .iter()
+                    .map(|v| representatives[*v])
+                    .collect();
+                let text = node.build_generator(None, &mut deps, options)?.to_string();
+                if index >= entrypoints.len() {
+                    if let Some(found) = generated.iter().position(|v| v.as_ref() == Some(&text)) {
+                        representatives
+                            .iter_mut()
+                            .filter(|v| **v == index)
+                            .for_each(|v| *v = found);
+                        generated.push(None);
+                        changed = true;
+                        continue;
+                    }
+                }
+                generated.push(Some(text));
+            }
+        }
+
+        // final index of each remaining source
+        let mut final_indexes = vec![0; nodes.len()];
+        let mut sources_count = 0;
+        for (index, representative) in representatives.iter().enumerate() {
+            if *representative == index {
+                final_indexes[index] = sources_count;
+                sources_count += 1;
+            }
+        }
+
+        // sources count of a rainlang bytecode is a single byte
+        if sources_count > MAX_SOURCES {
+            return Err(ComposeError::Reject(format!(
+                "composing {} sources exceeds the maximum of {} sources",
+                sources_count, MAX_SOURCES
+            )));
+        }
+
         // represents sourcemap details of each composing node
         let mut sourcemaps: Vec<ComposeSourcemap> = vec![];
 
@@ -196,62 +668,43 @@ impl RainDocument {
             ..Default::default()
         };
 
-        // build composing sourcemap struct for each target node
+        // build composing sourcemap struct for each remaining target node
         for (index, node) in nodes.iter().enumerate() {
-            let generator = &mut MagicString::new(node.element.content);
-            if let Some(deps) = deps_indexes.pop_front().as_mut() {
-                // sourcemap binding name comment
-                generator
-                    .prepend(&node.build_name_comment(index))
-                    .or(Err("could not build sourcemap".to_owned()))
-                    .map_err(ComposeError::Reject)?;
-
-                // sourcemap pragmas
-                for PragmaStatement { sources, .. } in &node.element.item.pragmas {
-                    for (source, literal) in sources {
-                        if let Some(v) = literal {
-                            generator
-                                .overwrite(
-                                    source.1[0] as i64,
-                                    source.1[1] as i64,
-                                    v,
-                                    OverwriteOptions::default(),
-                                )
-                                .or(Err("could not build sourcemap".to_owned()))
-                                .map_err(ComposeError::Reject)?;
-                        }
-                    }
-                }
-                build_sourcemap(
-                    node.element
-                        .item
-                        .ast
-                        .iter()
-                        .flat_map(|src| src.lines.iter().flat_map(|line| &line.nodes)),
-                    generator,
-                    deps,
-                )
-                .map_err(ComposeError::Reject)?;
-
-                sourcemaps.push(ComposeSourcemap {
-                    target: node.clone(),
-                    generated_string: generator.to_string(),
-                    mappings: generator
-                        .generate_decoded_map(mapping_opts.clone())
-                        .or(Err(ComposeError::Reject(
-                            "cannot build sourcemap".to_owned(),
-                        )))?
-                        .mappings,
-                })
-            } else {
-                return Err(ComposeError::Reject(
-                    "cannot resolve dependecies".to_owned(),
-                ));
+            if representatives[index] != index {
+                continue;
             }
+            let mut deps = deps_indexes[index]
+                .iter()
+                .map(|v| final_indexes[representatives[*v]])
+                .collect();
+            let generator = node.build_generator(
+                options.name_comments.then_some(final_indexes[index]),
+                &mut deps,
+                options,
+            )?;
+            sourcemaps.push(ComposeSourcemap {
+                target: node.clone(),
+                generated_string: generator.to_string(),
+                mappings: generator
+                    .generate_decoded_map(mapping_opts.clone())
+                    .or(Err(ComposeError::Reject(
+                        "cannot build sourcemap".to_owned(),
+                    )))?
+                    .mappings,
+            })
         }
         Ok(sourcemaps)
     }
 
+    /// builds the header comment if it is enabled by the given options
+    fn build_header(&self, options: &ComposeOptions) -> Result<Option<String>, ComposeError> {
+        if !options.header {
+            return Ok(None);
+        }
+        let hash = self.dotrain_meta_hash()?;
+        Ok(Some("/* dotrain: ".to_string() + &hash + " */ \n"))
+    }
+
     /// resolves dependencies recuresively
     /// this means resolving deps of deps recursively as long as any of them have nested deps
     /// gathers all the deps into 'nodes' array for building the sourcemap and returns deps indexes
@@ -263,8 +716,8 @@ impl RainDocument {
     fn resolve_deps<'a>(
         &'a self,
         nodes: &mut Vec<ComposeTarget<'a>>,
-    ) -> Result<VecDeque<VecDeque<u8>>, ComposeError> {
-        let mut deps_indexes: VecDeque<VecDeque<u8>> = VecDeque::new();
+    ) -> Result<VecDeque<VecDeque<usize>>, ComposeError> {
+        let mut deps_indexes: VecDeque<VecDeque<usize>> = VecDeque::new();
         let mut len = nodes.len();
         let mut ignore_offset = 0;
         while len - ignore_offset > 0 {
@@ -274,23 +727,29 @@ impl RainDocument {
                 for dep in &node.element.item.dependencies {
                     match search_namespace(dep, node.namespace, &node.namespace_path) {
                         Ok((parent_node, leaf, binding, namespace_path)) => {
-                            if !binding.problems.is_empty() {
-                                return Err(ComposeError::from_problems(
-                                    &binding.problems,
+                            let errors = error_problems(&binding.problems);
+                            if !errors.is_empty() {
+                                return Err(self.compose_problems(
+                                    &errors,
+                                    0,
                                     leaf.import_index,
-                                    &self.imports,
+                                    &leaf.hash,
                                 ));
                             }
-                            let rainlang_doc = RainlangDocument::create(
+                            let mut rainlang_doc = RainlangDocument::create(
                                 binding.content.clone(),
                                 parent_node,
-                                None,
+                                self.import_words(&leaf.hash),
+                                Some(&self.meta_store),
                             );
-                            if !rainlang_doc.problems.is_empty() {
-                                return Err(ComposeError::from_problems(
-                                    &rainlang_doc.problems,
+                            rainlang_doc.check_word_specs(&self.word_specs);
+                            let errors = error_problems(&rainlang_doc.problems);
+                            if !errors.is_empty() {
+                                return Err(self.compose_problems(
+                                    &errors,
+                                    binding.content_position[0],
                                     leaf.import_index,
-                                    &self.imports,
+                                    &leaf.hash,
                                 ));
                             }
                             // first search in composing nodes list to see if the current dep
@@ -309,16 +768,16 @@ impl RainDocument {
                                 .enumerate()
                                 .find(|(_, found)| **found == new_compse_target)
                             {
-                                this_node_deps_indexes.push_back(*index as u8);
+                                this_node_deps_indexes.push_back(*index);
                             } else if let Some((index, _)) = &new_nested_nodes
                                 .iter()
                                 .enumerate()
                                 .find(|(_, found)| **found == new_compse_target)
                             {
-                                this_node_deps_indexes.push_back((nodes.len() + index) as u8);
+                                this_node_deps_indexes.push_back(nodes.len() + index);
                             } else {
                                 this_node_deps_indexes
-                                    .push_back((nodes.len() + new_nested_nodes.len()) as u8);
+                                    .push_back(nodes.len() + new_nested_nodes.len());
                                 new_nested_nodes.push(new_compse_target);
                             }
                         }
@@ -337,18 +796,138 @@ impl RainDocument {
     }
 }
 
-fn validate_dep_path(index: usize, deps: &VecDeque<VecDeque<u8>>, path: &[u8]) -> Result<(), ()> {
+fn validate_dep_path(
+    index: usize,
+    deps: &VecDeque<VecDeque<usize>>,
+    path: &[usize],
+) -> Result<(), ()> {
     for dep in &deps[index] {
         let mut current_path = path.to_vec();
         if current_path.contains(dep) {
             return Err(());
         }
         current_path.push(*dep);
-        validate_dep_path(*dep as usize, deps, &current_path)?;
+        validate_dep_path(*dep, deps, &current_path)?;
     }
     Ok(())
 }
 
+/// Builds the edits of comments and whitespaces of a rainlang text based on the given options,
+/// whitespaces are kept only where they separate two tokens
+fn trivia_edits(text: &str, options: &ComposeOptions) -> Vec<(Offsets, String)> {
+    let bytes = text.as_bytes();
+
+    // find whitespaces and comments by skipping over string and sub parser literals
+    let mut trivia: Vec<(Offsets, bool)> = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i = string_literal_end(&text[i..])
+                    .map(|v| i + v)
+                    .unwrap_or(bytes.len());
+            }
+            b'[' => {
+                i = text[i + 1..]
+                    .find(']')
+                    .map(|v| i + v + 2)
+                    .unwrap_or(bytes.len());
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = text[i + 2..]
+                    .find("*/")
+                    .map(|v| i + v + 4)
+                    .unwrap_or(bytes.len());
+                trivia.push(([i, end], true));
+                i = end;
+            }
+            v if v.is_ascii_whitespace() => {
+                let start = i;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                trivia.push(([start, i], false));
+            }
+            _ => i += 1,
+        }
+    }
+
+    // group adjacent trivia and build the replacement of each group
+    let is_tight = |c: Option<char>| c.is_none_or(|c| ",;()<>".contains(c));
+    let mut edits = vec![];
+    let mut index = 0;
+    while index < trivia.len() {
+        let start = trivia[index].0[0];
+        let mut group = vec![];
+        while let Some(item) = trivia.get(index) {
+            if group.is_empty() || trivia[index - 1].0[1] == item.0[0] {
+                group.push(*item);
+                index += 1;
+            } else {
+                break;
+            }
+        }
+        let end = group.last().map_or(start, |v| v.0[1]);
+        let tight = is_tight(text[..start].chars().last()) || is_tight(text[end..].chars().next());
+
+        let replacement = if options.minify {
+            let comments = group
+                .iter()
+                .filter(|v| v.1 && !options.strip_comments)
+                .map(|v| &text[v.0[0]..v.0[1]])
+                .collect::<Vec<_>>()
+                .join(" ");
+            if comments.is_empty() {
+                if tight { "" } else { " " }.to_owned()
+            } else {
+                let mut replacement = comments;
+                if !is_tight(text[..start].chars().last()) {
+                    replacement.insert(0, ' ');
+                }
+                if !is_tight(text[end..].chars().next()) {
+                    replacement.push(' ');
+                }
+                replacement
+            }
+        } else {
+            // removes comments along with the spaces before them, and the line break after
+            // them if they are on their own line
+            let mut replacement = String::new();
+            let mut own_line = false;
+            for (position, is_comment) in &group {
+                let item = &text[position[0]..position[1]];
+                if *is_comment {
+                    while replacement.ends_with([' ', '\t']) {
+                        replacement.pop();
+                    }
+                    own_line =
+                        replacement.ends_with('\n') || (replacement.is_empty() && start == 0);
+                } else if own_line {
+                    replacement.push_str(item.split_once('\n').map_or("", |v| v.1));
+                    own_line = false;
+                } else {
+                    replacement.push_str(item);
+                }
+            }
+            if replacement.is_empty() && !tight {
+                replacement.push(' ');
+            }
+            replacement
+        };
+        if replacement != text[start..end] {
+            edits.push(([start, end], replacement));
+        }
+    }
+    edits
+}
+
+/// Returns the offsets of the start of each line of the given text
+fn line_starts(text: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+    starts
+}
+
 /// Searchs in a Namespace for a given name
 fn search_namespace<'a>(
     name: &str,
@@ -416,21 +995,19 @@ fn search_namespace<'a>(
 fn build_sourcemap<'a>(
     nodes: impl Iterator<Item = &'a Node>,
     generator: &mut MagicString,
-    deps_indexes: &mut VecDeque<u8>,
+    deps_indexes: &mut VecDeque<usize>,
 ) -> Result<(), String> {
     for node in nodes {
         match node {
-            Node::Literal(literal) => {
-                if literal.id.is_some() {
-                    generator
-                        .overwrite(
-                            literal.position[0] as i64,
-                            literal.position[1] as i64,
-                            &literal.value,
-                            OverwriteOptions::default(),
-                        )
-                        .or(Err("could not build sourcemap".to_owned()))?;
-                }
+            Node::Literal(literal) if literal.id.is_some() => {
+                generator
+                    .overwrite(
+                        literal.position[0] as i64,
+                        literal.position[1] as i64,
+                        &literal.value,
+                        OverwriteOptions::default(),
+                    )
+                    .or(Err("could not build sourcemap".to_owned()))?;
             }
             Node::Opcode(opcode) => {
                 let args_details = if let Some(operand_args) = &opcode.operand_args {
@@ -489,23 +1066,132 @@ fn build_sourcemap<'a>(
     Ok(())
 }
 
-impl ComposeError {
-    fn from_problems(problems: &[Problem], import_index: isize, imports: &[Import]) -> Self {
-        Self::Problems(
+impl RainDocument {
+    /// builds a ComposeError from the given problems of a binding in this instance or in one of its
+    /// imports, the offset is applied to problems positions, problems of an imported binding are
+    /// positioned at the import hash while their actual location is kept in their origin chain
+    fn compose_problems(
+        &self,
+        problems: &[Problem],
+        offset: usize,
+        import_index: isize,
+        hash: &str,
+    ) -> ComposeError {
+        ComposeError::Problems(
             problems
                 .iter()
-                .map(|p| Problem {
-                    msg: p.msg.clone(),
-                    code: p.code,
-                    position: if import_index == -1 {
-                        p.position
+                .map(|p| {
+                    let position = [p.position[0] + offset, p.position[1] + offset];
+                    if import_index == -1 {
+                        return Problem {
+                            msg: p.msg.clone(),
+                            code: p.code,
+                            severity: p.severity,
+                            position,
+                            origin: p.origin.clone(),
+                        };
+                    }
+                    let import = &self.imports[import_index as usize];
+
+                    // chain of the imports leading to the dotrain of the given hash with
+                    // each one pointing to the next import's hash in its own text
+                    let chain = if import.hash == hash {
+                        vec![import]
                     } else {
-                        imports[import_index as usize].hash_position
-                    },
+                        import
+                            .sequence
+                            .as_ref()
+                            .and_then(|v| v.dotrain.as_ref())
+                            .and_then(|v| import_chain(&v.imports, hash))
+                            .map(|mut v| {
+                                v.insert(0, import);
+                                v
+                            })
+                            .unwrap_or(vec![import])
+                    };
+                    let mut origin = chain
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| ProblemOrigin {
+                            hash: v.hash.clone(),
+                            uri: self.import_uri(&v.hash),
+                            position: chain.get(i + 1).map_or(position, |v| v.hash_position),
+                        })
+                        .collect::<Vec<_>>();
+                    origin.extend(p.origin.clone().unwrap_or_default());
+                    Problem {
+                        msg: p.msg.clone(),
+                        code: p.code,
+                        severity: p.severity,
+                        position: import.hash_position,
+                        origin: Some(origin),
+                    }
                 })
                 .collect(),
         )
     }
+
+    /// known words of the dotrain of the given hash, ie this instance's words if the hash is empty
+    /// or the words of the imported dotrain the hash belongs to
+    fn import_words(&self, hash: &str) -> Option<&AuthoringMeta> {
+        if hash.is_empty() {
+            return self.known_words.as_ref();
+        }
+        import_chain(&self.imports, hash)?
+            .last()?
+            .sequence
+            .as_ref()?
+            .dotrain
+            .as_ref()?
+            .known_words
+            .as_ref()
+    }
+}
+
+/// Problems of the given problems that have error severity
+fn error_problems(problems: &[Problem]) -> Vec<Problem> {
+    problems
+        .iter()
+        .filter(|v| v.severity == Severity::Error)
+        .cloned()
+        .collect()
+}
+
+/// Reads the entrypoints listed under the `entrypoints` key of the given parsed front matter,
+/// either as a flow or block list (`entrypoints: [a, b]` or `- a` items) or as a comma separated
+/// string (`entrypoints: a, b`)
+fn front_matter_entrypoints(front_matter: Option<Value>) -> Vec<String> {
+    let items = match front_matter.as_ref().and_then(|v| v.get(ENTRYPOINTS_KEY)) {
+        Some(Value::String(value)) => value.split(',').map(str::trim).collect(),
+        Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+    let mut entrypoints: Vec<String> = vec![];
+    for item in items {
+        if !item.is_empty() && !entrypoints.iter().any(|v| v == item) {
+            entrypoints.push(item.to_owned());
+        }
+    }
+    entrypoints
+}
+
+/// Searches the given imports recursively for the chain of imports leading to the dotrain of the given hash
+fn import_chain<'a>(imports: &'a [Import], hash: &str) -> Option<Vec<&'a Import>> {
+    for import in imports {
+        if import.hash == hash {
+            return Some(vec![import]);
+        }
+        if let Some(mut chain) = import
+            .sequence
+            .as_ref()
+            .and_then(|v| v.dotrain.as_ref())
+            .and_then(|v| import_chain(&v.imports, hash))
+        {
+            chain.insert(0, import);
+            return Some(chain);
+        }
+    }
+    None
 }
 
 #[cfg(test)]
@@ -564,6 +1250,7 @@ _: opcode-1(0xabcd 456);
             &["exp-binding"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = "/* 0. exp-binding */ \n_: opcode-1(0xabcd 456);";
@@ -585,6 +1272,7 @@ some-name _: opcode-2(opcode-1(1 2) const-binding) 0xab34;
             &["exp-binding"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = "/* 0. exp-binding */ 
@@ -610,6 +1298,7 @@ _: opcode-2(0xabcd some-value);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = "/* 0. exp-binding-1 */ 
@@ -641,6 +1330,7 @@ _: opcode-2(0xabcd some-value);
             &["exp-binding-1", "exp-binding-2"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = "/* 0. exp-binding-1 */ 
@@ -673,6 +1363,7 @@ _: opcode-2(some-name some-other-value);
             &["main", "exp-binding-1", "exp-binding-2"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = "/* 0. main */ 
@@ -702,6 +1393,7 @@ _: some-sub-parser-word<1 2>(some-value some-other-value);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = "/* 0. exp-binding-1 */ 
@@ -723,6 +1415,7 @@ _: some-sub-parser-word<1 2>(some-value literal-binding);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
@@ -745,6 +1438,7 @@ _: some-sub-parser-word<some-value " some literal as operand " "test">(some-valu
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
@@ -769,6 +1463,7 @@ _: some-sub-parser-word<1 2>(some-value 44);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
@@ -794,6 +1489,7 @@ _: opcode-1(0xabcd some-value---);
             &["exp-binding-1---"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r"/* 0. exp-binding-1--- */ 
@@ -821,6 +1517,7 @@ _: opcode-3(some-value some-other-value);
             &["exp-binding-1", "main"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r"/* 0. exp-binding-1 */ 
@@ -853,6 +1550,7 @@ _: opcode-3(some-value some-other-value);
             &["exp-binding-1", "main"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r"/* 0. exp-binding-1 */ 
@@ -885,6 +1583,7 @@ _: opcode-1(0xabcd 456);
             &["exp-binding-1", "exp-binding-2"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::OddLenHex.to_problem(vec![], [85, 98])
@@ -907,6 +1606,7 @@ _: opcode-1(0xabcd elided);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::ElidedBinding.to_problem(vec!["elided", "this is elided"], [123, 129])
@@ -926,6 +1626,7 @@ _: opcode-1(0xabcd elided);
             &["exp-binding"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Reject(
             "undefined identifier: exp-binding".to_owned(),
@@ -943,6 +1644,7 @@ _: opcode-1(0xabcd elided);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::NoFrontMatterSplitter.to_problem(vec![], [0, 0]),
@@ -961,6 +1663,7 @@ _: opcode-1(0xabcd elided);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::UnexpectedToken.to_problem(vec![], [43, 45])
@@ -979,6 +1682,7 @@ _: opcode-1(0xabcd elided);
             &["exp-binding-1---"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::UnexpectedToken.to_problem(vec![], [88, 90]),
@@ -1009,6 +1713,7 @@ _: opcode-2(some-name some-other-value);
             &["exp-binding-1", "main"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::CircularDependency.to_problem(vec![], [86, 99])
@@ -1034,6 +1739,7 @@ _: opcode-1<exp-binding-2>(0xabcd 456);
             &["exp-binding-1", "main"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::CircularDependency.to_problem(vec![], [112, 125])
@@ -1062,6 +1768,7 @@ _: opcode(1 2);
             &["exp-binding-1", "main"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::DeepQuote.to_problem(vec![], [112, 125])
@@ -1082,6 +1789,7 @@ _: some-sub-parser-word<1 2>(4e18 literal-binding);
             &["exp-binding-1"],
             Some(meta_store.clone()),
             None,
+            None,
         )
         .unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
@@ -1106,6 +1814,7 @@ _: some-sub-parser-word<1 2>(4e18 literal-binding);
             &["exp-binding-1", "main"],
             Some(meta_store.clone()),
             None,
+            None,
         );
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::UndefinedIdentifier.to_problem(vec!["extra"], [152, 157]),
@@ -1125,7 +1834,7 @@ _: some-sub-parser-word<1 2>(4e18 literal-binding);
 _: opcode-1(0xabcd "something.else");
 "#;
         let rainlang_text =
-            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None).unwrap();
+            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None, None).unwrap();
         let expected_rainlang = r#"/* 0. exp-binding */ 
 _: opcode-1(0xabcd "something.else");"#;
         assert_eq!(rainlang_text, expected_rainlang);
@@ -1141,7 +1850,7 @@ _: opcode-1(0xabcd "something.else");"#;
 _: opcode-1(12.34e6 123.123);
 "#;
         let rainlang_text =
-            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None).unwrap();
+            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None, None).unwrap();
         let expected_rainlang = r#"/* 0. exp-binding */ 
 _: opcode-1(12.34e6 123.123);"#;
         assert_eq!(rainlang_text, expected_rainlang);
@@ -1152,14 +1861,14 @@ _: opcode-1(12.34e6 123.123);"#;
         matter
 ---
 #exp-binding
-_some-lhs-word: opcode-1(12 123.456e123),
+_some-lhs-word: opcode-1(12 123.456e73),
 _some-other-lhs-word: 1e18,
 _ _: opcode-1(_some-lhs-word _some-other-lhs-word) _some-other-lhs-word;
 "#;
         let rainlang_text =
-            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None).unwrap();
+            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None, None).unwrap();
         let expected_rainlang = r#"/* 0. exp-binding */ 
-_some-lhs-word: opcode-1(12 123.456e123),
+_some-lhs-word: opcode-1(12 123.456e73),
 _some-other-lhs-word: 1e18,
 _ _: opcode-1(_some-lhs-word _some-other-lhs-word) _some-other-lhs-word;"#;
         assert_eq!(rainlang_text, expected_rainlang);
@@ -1173,7 +1882,7 @@ _ _: opcode-1(_some-lhs-word _some-other-lhs-word) _some-other-lhs-word;"#;
 #exp-binding
 _some-lhs-word: opcode-1(12 _bad-binding-name);
 "#;
-        let result = RainDocument::compose_text(dotrain_text, &["exp-binding-1"], None, None);
+        let result = RainDocument::compose_text(dotrain_text, &["exp-binding-1"], None, None, None);
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::InvalidWordPattern.to_problem(vec!["_bad-binding-name"], [50, 67])
         ]));
@@ -1190,7 +1899,7 @@ _some-lhs-word: opcode-1(12 _bad-binding-name);
 _: opcode-1(12.34e-6 1.234e-15);
 "#;
         let rainlang_text =
-            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None).unwrap();
+            RainDocument::compose_text(dotrain_text, &["exp-binding"], None, None, None).unwrap();
         let expected_rainlang = r#"/* 0. exp-binding */ 
 _: opcode-1(12.34e-6 1.234e-15);"#;
         assert_eq!(rainlang_text, expected_rainlang);
@@ -1205,7 +1914,7 @@ _: opcode-1(12.34e-6 1.234e-15);"#;
 #exp-binding
 _some-lhs-word: opcode-1(12 1);
 "#;
-        let result = RainDocument::compose_text(dotrain_text, &["exp-binding-1"], None, None);
+        let result = RainDocument::compose_text(dotrain_text, &["exp-binding-1"], None, None, None);
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::DuplicateIdentifier.to_problem(vec![], [64, 72])
         ]));
@@ -1237,7 +1946,7 @@ _: opcode-2(some-name some-other-value);
         let rebinds = vec![Rebind("some-override-value".to_owned(), "567".to_owned())];
         block_on(rain_document.parse(false, Some(rebinds)));
         let rainlang_text = rain_document
-            .compose(&["exp-binding-1", "exp-binding-2"])
+            .compose(&["exp-binding-1", "exp-binding-2"], None)
             .unwrap();
         let expected_rainlang = "/* 0. exp-binding-1 */ 
 _: opcode-1(0xabcd 456);
@@ -1273,7 +1982,7 @@ _: opcode-2(some-name some-other-value);
         ];
         block_on(rain_document.parse(false, Some(rebinds)));
         let rainlang_text = rain_document
-            .compose(&["exp-binding-1", "exp-binding-2"])
+            .compose(&["exp-binding-1", "exp-binding-2"], None)
             .unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
 _: opcode-1(0xabcd 456);
@@ -1313,7 +2022,7 @@ _: opcode-2(some-name some-other-value);
         ];
         block_on(rain_document.parse(false, Some(rebinds)));
         let rainlang_text = rain_document
-            .compose(&["exp-binding-1", "exp-binding-2"])
+            .compose(&["exp-binding-1", "exp-binding-2"], None)
             .unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
 _: opcode-1(0xabcd 456);
@@ -1358,7 +2067,7 @@ _: opcode(1 2);
             ),
         ];
         block_on(rain_document.parse(false, Some(rebinds)));
-        let rainlang_text = rain_document.compose(&["exp-binding-1"]).unwrap();
+        let rainlang_text = rain_document.compose(&["exp-binding-1"], None).unwrap();
         let expected_rainlang = r#"/* 0. exp-binding-1 */ 
 _: opcode-1<1>(0xabcd 456);
 
@@ -1380,7 +2089,7 @@ _: opcode-1(0xabcd 456);
             "567".to_owned(),
         )];
         block_on(rain_document.parse(false, Some(rebinds)));
-        let result = rain_document.compose(&["exp-binding-1"]);
+        let result = rain_document.compose(&["exp-binding-1"], None);
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::InvalidSuppliedRebindings
                 .to_problem(vec!["rebind too deep: deep.some-override-value"], [0, 0]),
@@ -1398,7 +2107,7 @@ _: opcode-1(0xabcd 456);
             "'some-quote".to_owned(),
         )];
         block_on(rain_document.parse(false, Some(rebinds)));
-        let result = rain_document.compose(&["exp-binding-1"]);
+        let result = rain_document.compose(&["exp-binding-1"], None);
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::InvalidSuppliedRebindings
                 .to_problem(vec!["rebind too deep: deep.some-quote-binding"], [0, 0]),
@@ -1433,7 +2142,7 @@ _: opcode-1(0xabcd 456);
             Rebind("rebind-item".to_owned(), "'another-binding".to_owned()),
         ];
         block_on(rain_document.parse(false, Some(rebinds)));
-        let rainlang_text = rain_document.compose(&["e1", "e2"]).unwrap();
+        let rainlang_text = rain_document.compose(&["e1", "e2"], None).unwrap();
         let expected_rainlang = r#"/* 0. e1 */ 
 _: opcode-3(1 call<2>());
 
@@ -1464,7 +2173,7 @@ _: opcode-2(1 0 [something]);
             Rebind("b".to_owned(), "'some-other-binding".to_owned()),
         ];
         block_on(rain_document.parse(false, Some(rebinds)));
-        let rainlang_text = rain_document.compose(&["some-binding"]).unwrap();
+        let rainlang_text = rain_document.compose(&["some-binding"], None).unwrap();
         let expected_rainlang = "/* 0. some-binding */ 
 _: opcode-2<0 1>(1 0 [something]);
 
@@ -1480,7 +2189,7 @@ _: opcode-1(0xabcd 456);
             RainDocument::new(dotrain_text.to_owned(), Some(meta_store.clone()), 0, None);
         let rebinds = vec![Rebind("non-existant-binding".to_owned(), "567".to_owned())];
         block_on(rain_document.parse(false, Some(rebinds)));
-        let result = rain_document.compose(&["exp-binding-1"]);
+        let result = rain_document.compose(&["exp-binding-1"], None);
         let expected_err = Err(ComposeError::Problems(vec![
             ErrorCode::InvalidSuppliedRebindings
                 .to_problem(vec!["undefined binding: non-existant-binding"], [0, 0]),
@@ -1488,6 +2197,500 @@ _: opcode-1(0xabcd 456);
         assert_eq!(result, expected_err);
     }
 
+    #[test]
+    fn test_compose_import_words() {
+        let word = |word: &str| AuthoringMetaItem {
+            word: word.to_owned(),
+            operand_parser_offset: 0,
+            description: String::new(),
+        };
+        let mut store = rain_metadata::Store::new();
+        let (import_hash, _) = store
+            .set_dotrain("---\n#exp\n_: sub(1 2);\n", "file:///import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let dotrain_text = format!("---\n@imp {}\n#main\n_: add<'imp.exp>(1 2);\n", import_hash);
+        let mut rain_document = RainDocument::create(
+            dotrain_text,
+            Some(Arc::new(RwLock::new(store))),
+            Some(AuthoringMeta(vec![word("add")])),
+            None,
+        );
+        assert!(rain_document.compose(&["main"], None).is_err());
+
+        // imported bindings are checked against the words of the dotrain they come from
+        rain_document.imports[0]
+            .sequence
+            .as_mut()
+            .unwrap()
+            .dotrain
+            .as_mut()
+            .unwrap()
+            .known_words = Some(AuthoringMeta(vec![word("sub")]));
+        let result = rain_document.compose(&["main"], None);
+        assert_eq!(
+            result,
+            Ok("/* 0. main */ \n_: add<1>(1 2);\n\n/* 1. .imp.exp */ \n_: sub(1 2);".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_compose_with_sourcemap() {
+        let mut store = rain_metadata::Store::new();
+        let import_text = r"---
+#value 4e18
+#exp
+_: opcode-2(value 1);
+";
+        let (import_hash, _) = store
+            .set_dotrain(import_text, "file:///import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let meta_store = Arc::new(RwLock::new(store));
+
+        let dotrain_text = format!(
+            r"---
+@imp {}
+#some-value 0xabcd
+#main
+_: opcode-1<'imp.exp>(some-value 456);
+",
+            import_hash
+        );
+        let rain_document =
+            RainDocument::create(dotrain_text.clone(), Some(meta_store), None, None);
+        let result = rain_document
+            .compose_with_sourcemap(&["main"], None)
+            .unwrap();
+        let expected_rainlang = "/* 0. main */ 
+_: opcode-1<1>(0xabcd 456);
+
+/* 1. .imp.exp */ 
+_: opcode-2(4e18 1);";
+        assert_eq!(result.text, expected_rainlang);
+        assert_eq!(result.text, rain_document.compose(&["main"], None).unwrap());
+
+        let original_text = |mapping: &ComposeMapping| {
+            if mapping.import_index == -1 {
+                &dotrain_text[mapping.original[0]..mapping.original[1]]
+            } else {
+                &import_text[mapping.original[0]..mapping.original[1]]
+            }
+        };
+
+        // unedited fragments map to identical original text
+        for mapping in &result.mappings {
+            let generated = &result.text[mapping.generated[0]..mapping.generated[1]];
+            if generated.len() == mapping.original[1] - mapping.original[0] {
+                assert_eq!(generated, original_text(mapping));
+            }
+        }
+
+        let mapping = result.lookup(0).unwrap();
+        assert_eq!(original_text(mapping), "main");
+        assert_eq!(mapping.import_index, -1);
+
+        let mapping = result.lookup(result.text.find("<1>").unwrap() + 1).unwrap();
+        assert_eq!(original_text(mapping), "'imp.exp");
+
+        let mapping = result.lookup(result.text.find("0xabcd").unwrap()).unwrap();
+        assert_eq!(original_text(mapping), "some-value");
+        assert_eq!(mapping.hash, "");
+
+        let mapping = result.lookup(result.text.find("4e18").unwrap()).unwrap();
+        assert_eq!(original_text(mapping), "value");
+        assert_eq!(mapping.hash, import_hash);
+        assert_eq!(mapping.import_index, 0);
+
+        let mapping = result
+            .lookup(result.text.find("opcode-2").unwrap())
+            .unwrap();
+        assert_eq!(original_text(mapping), "_: opcode-2(");
+
+        assert_eq!(result.lookup(result.text.len()), None);
+    }
+
+    #[test]
+    fn test_compose_expression() {
+        let mut store = rain_metadata::Store::new();
+        let import_text = r"---
+#exp
+_: opcode-2(1 2);
+";
+        let (import_hash, _) = store
+            .set_dotrain(import_text, "file:///import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let meta_store = Arc::new(RwLock::new(store));
+
+        let dotrain_text = format!(
+            r"---
+@imp {}
+#sub-parser 0x1234
+#main
+using-words-from sub-parser 0xabcd
+_: opcode-1<'imp.exp>(1 2);
+#other
+_: opcode-2(3);
+",
+            import_hash
+        );
+        let rain_document = RainDocument::create(dotrain_text, Some(meta_store), None, None);
+        let result = rain_document
+            .compose_expression(&["main", "other"], None)
+            .unwrap();
+        let expected = ComposedExpression {
+            header: None,
+            sources: vec![
+                ComposedSource {
+                    index: 0,
+                    namespace_path: String::new(),
+                    name: "main".to_owned(),
+                    hash: String::new(),
+                    pragmas: vec!["0x1234".to_owned(), "0xabcd".to_owned()],
+                    text: "/* 0. main */ \nusing-words-from 0x1234 0xabcd\n_: opcode-1<2>(1 2);"
+                        .to_owned(),
+                },
+                ComposedSource {
+                    index: 1,
+                    namespace_path: String::new(),
+                    name: "other".to_owned(),
+                    hash: String::new(),
+                    pragmas: vec![],
+                    text: "/* 1. other */ \n_: opcode-2(3);".to_owned(),
+                },
+                ComposedSource {
+                    index: 2,
+                    namespace_path: ".imp".to_owned(),
+                    name: "exp".to_owned(),
+                    hash: import_hash,
+                    pragmas: vec![],
+                    text: "/* 2. .imp.exp */ \n_: opcode-2(1 2);".to_owned(),
+                },
+            ],
+        };
+        assert_eq!(result, expected);
+        assert_eq!(
+            result.to_rainlang(),
+            rain_document.compose(&["main", "other"], None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compose_large_document() {
+        // builds a chain of bindings where each one depends on the next one
+        let build_text = |count: usize| {
+            let mut text = "---\n".to_owned();
+            for i in 0..count {
+                if i == count - 1 {
+                    text.push_str(&format!("#exp-{}\n_: opcode-1(1 2);\n", i));
+                } else {
+                    text.push_str(&format!("#exp-{}\n_: opcode-1<'exp-{}>(1 2);\n", i, i + 1));
+                }
+            }
+            text
+        };
+
+        let rain_document = RainDocument::create(build_text(MAX_SOURCES), None, None, None);
+        let result = rain_document.compose_expression(&["exp-0"], None).unwrap();
+        assert_eq!(result.sources.len(), MAX_SOURCES);
+        let expected_source = format!(
+            "/* {0}. exp-{0} */ \n_: opcode-1<{1}>(1 2);",
+            MAX_SOURCES - 2,
+            MAX_SOURCES - 1
+        );
+        assert_eq!(result.sources[MAX_SOURCES - 2].text, expected_source);
+
+        let text = build_text(MAX_SOURCES + 1);
+        let result = RainDocument::compose_text(&text, &["exp-0"], None, None, None);
+        let expected_err = Err(ComposeError::Reject(
+            "composing 256 sources exceeds the maximum of 255 sources".to_owned(),
+        ));
+        assert_eq!(result, expected_err);
+    }
+
+    #[test]
+    fn test_compose_options() {
+        let dotrain_text = r#"---
+#some-value 4e18
+#main
+/* main comment */
+using-words-from 0x1234
+_: opcode-1(
+    some-value   456
+),
+/* second line */
+a b: " a   string " 0x01;
+"#;
+        let rain_document = RainDocument::create(dotrain_text.to_owned(), None, None, None);
+
+        let options = ComposeOptions {
+            name_comments: false,
+            ..Default::default()
+        };
+        let result = rain_document.compose(&["main"], Some(options)).unwrap();
+        let expected_rainlang = r#"/* main comment */
+using-words-from 0x1234
+_: opcode-1(
+    4e18   456
+),
+/* second line */
+a b: " a   string " 0x01;"#;
+        assert_eq!(result, expected_rainlang);
+
+        let options = ComposeOptions {
+            strip_comments: true,
+            ..Default::default()
+        };
+        let result = rain_document.compose(&["main"], Some(options)).unwrap();
+        let expected_rainlang = r#"/* 0. main */ 
+using-words-from 0x1234
+_: opcode-1(
+    4e18   456
+),
+a b: " a   string " 0x01;"#;
+        assert_eq!(result, expected_rainlang);
+
+        let options = ComposeOptions {
+            name_comments: false,
+            minify: true,
+            ..Default::default()
+        };
+        let result = rain_document.compose(&["main"], Some(options)).unwrap();
+        let expected_rainlang = r#"/* main comment */ using-words-from 0x1234 _: opcode-1(4e18 456),/* second line */ a b: " a   string " 0x01;"#;
+        assert_eq!(result, expected_rainlang);
+
+        let result = rain_document
+            .compose_with_sourcemap(&["main"], Some(ComposeOptions::minimal()))
+            .unwrap();
+        let expected_rainlang =
+            r#"using-words-from 0x1234 _: opcode-1(4e18 456),a b: " a   string " 0x01;"#;
+        assert_eq!(result.text, expected_rainlang);
+        let mapping = result.lookup(result.text.find("4e18").unwrap()).unwrap();
+        assert_eq!(
+            &dotrain_text[mapping.original[0]..mapping.original[1]],
+            "some-value"
+        );
+        let mapping = result.lookup(result.text.find("),a").unwrap() + 2).unwrap();
+        assert_eq!(
+            &dotrain_text[mapping.original[0]..mapping.original[0] + 4],
+            "a b:"
+        );
+
+        let options = ComposeOptions {
+            header: true,
+            ..ComposeOptions::minimal()
+        };
+        let result = rain_document
+            .compose_expression(&["main"], Some(options))
+            .unwrap();
+        let mut store = rain_metadata::Store::new();
+        let (hash, _) = store.set_dotrain(dotrain_text, "main.rain", false).unwrap();
+        let expected_header = format!(
+            "/* dotrain: {} */ \n",
+            alloy_primitives::hex::encode_prefixed(hash)
+        );
+        assert_eq!(result.header, Some(expected_header.clone()));
+        assert_eq!(
+            result.to_rainlang(),
+            expected_header
+                + r#"using-words-from 0x1234 _: opcode-1(4e18 456),a b: " a   string " 0x01;"#
+        );
+
+        // whitespaces after an escaped quote are still inside the string literal
+        let dotrain_text = r#"---
+#main
+_ _:   "a \"  b"   "c";
+"#;
+        let rain_document = RainDocument::create(dotrain_text.to_owned(), None, None, None);
+        let result = rain_document
+            .compose(&["main"], Some(ComposeOptions::minimal()))
+            .unwrap();
+        assert_eq!(result, r#"_ _: "a \"  b" "c";"#);
+    }
+
+    #[test]
+    fn test_compose_deduplicates_sources() {
+        let mut store = rain_metadata::Store::new();
+        let import_text = r"---
+#exp
+_: opcode-2<'inner>(1 2);
+#inner
+_: opcode-3(3 4);
+";
+        let (import_hash, _) = store
+            .set_dotrain(import_text, "file:///import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let meta_store = Arc::new(RwLock::new(store));
+
+        // same expressions reached through 2 different namespaces
+        let dotrain_text = format!(
+            r"---
+@a {0}
+@b {0}
+#main
+_: opcode-1<'a.exp 'b.exp>(1 2);
+#other
+_: opcode-3(3 4);
+",
+            import_hash
+        );
+        let rain_document = RainDocument::create(dotrain_text, Some(meta_store), None, None);
+        let result = rain_document.compose(&["main"], None).unwrap();
+        let expected_rainlang = "/* 0. main */ 
+_: opcode-1<1 1>(1 2);
+
+/* 1. .a.exp */ 
+_: opcode-2<2>(1 2);
+
+/* 2. .a.inner */ 
+_: opcode-3(3 4);";
+        assert_eq!(result, expected_rainlang);
+
+        // dependencies collapse into identical entrypoints as well
+        let result = rain_document.compose(&["main", "other"], None).unwrap();
+        let expected_rainlang = "/* 0. main */ 
+_: opcode-1<2 2>(1 2);
+
+/* 1. other */ 
+_: opcode-3(3 4);
+
+/* 2. .a.exp */ 
+_: opcode-2<1>(1 2);";
+        assert_eq!(result, expected_rainlang);
+    }
+
+    #[test]
+    fn test_compose_problems_origin() {
+        let mut store = rain_metadata::Store::new();
+        let import_text = r"---
+#exp
+_: opcode-2(1 undefined-value);
+";
+        let (import_hash, _) = store
+            .set_dotrain(import_text, "file:///import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let meta_store = Arc::new(RwLock::new(store));
+
+        let dotrain_text = format!(
+            r"---
+@imp {}
+#main
+_: opcode-1<'imp.exp>(1 2);
+",
+            import_hash
+        );
+        let rain_document = RainDocument::create(dotrain_text, Some(meta_store), None, None);
+        let result = rain_document.compose(&["main"], None);
+
+        // the problem is positioned at the import hash while its origin points
+        // to its actual location in the imported dotrain
+        let expected_err = ComposeError::Problems(vec![Problem {
+            msg: "undefined word: undefined-value".to_owned(),
+            position: [9, 75],
+            code: ErrorCode::UndefinedWord,
+            severity: Severity::Error,
+            origin: Some(vec![ProblemOrigin {
+                hash: import_hash.clone(),
+                uri: Some("file:///import.rain".to_owned()),
+                position: [23, 38],
+            }]),
+        }]);
+        assert_eq!(result, Err(expected_err));
+        assert_eq!(&import_text[23..38], "undefined-value");
+    }
+
+    #[test]
+    fn test_compose_variants() {
+        let dotrain_text = r"---
+#value 1
+#other-value 2
+#main
+_: opcode-1(value other-value);
+";
+        let rain_document = RainDocument::create(dotrain_text.to_owned(), None, None, None);
+        let variants = vec![
+            vec![Rebind("value".to_owned(), "4e18".to_owned())],
+            vec![
+                Rebind("value".to_owned(), "0x01".to_owned()),
+                Rebind("other-value".to_owned(), "5".to_owned()),
+            ],
+            vec![Rebind("undefined".to_owned(), "5".to_owned())],
+        ];
+        let result = rain_document.compose_variants(&["main"], variants.clone(), None);
+
+        // each variant is the same as parsing the text with its rebinds
+        let expected = variants
+            .into_iter()
+            .map(|rebinds| {
+                RainDocument::create(dotrain_text.to_owned(), None, None, Some(rebinds))
+                    .compose(&["main"], None)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+        assert_eq!(
+            result[1],
+            Ok("/* 0. main */ \n_: opcode-1(0x01 5);".to_owned())
+        );
+        assert!(result[2].is_err());
+
+        // the original instance is left untouched
+        assert_eq!(
+            rain_document.compose(&["main"], None),
+            Ok("/* 0. main */ \n_: opcode-1(1 2);".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_declared_entrypoints() {
+        let dotrain_text = r"entrypoints:
+  - main
+  - other
+---
+#main
+_: opcode-1(1 2);
+/* @entrypoint */
+#annotated
+_: opcode-2(3 4);
+/* not an entrypoint */
+#other
+_: opcode-3(5 6);
+";
+        let rain_document = RainDocument::create(dotrain_text.to_owned(), None, None, None);
+        assert_eq!(
+            rain_document.declared_entrypoints(),
+            vec!["main", "other", "annotated"]
+        );
+        let result = rain_document.compose_declared(None).unwrap();
+        let expected = rain_document
+            .compose(&["main", "other", "annotated"], None)
+            .unwrap();
+        assert_eq!(result, expected);
+
+        let entrypoints = |front_matter: &str| {
+            let text = format!("{front_matter}\n---\n#a 1");
+            RainDocument::create(text, None, None, None).declared_entrypoints()
+        };
+        assert_eq!(
+            entrypoints("name: x\nentrypoints: [a, 'b', \"c\", a]"),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(entrypoints("entrypoints : a, b"), vec!["a", "b"]);
+        assert_eq!(entrypoints("{\"entrypoints\": [\"a\", 1]}"), vec!["a"]);
+        assert_eq!(entrypoints("entrypoints:\n  - a\n  - 'b'"), vec!["a", "b"]);
+
+        let rain_document = RainDocument::create("---\n#main\n_: 1;".to_owned(), None, None, None);
+        assert!(rain_document.declared_entrypoints().is_empty());
+        assert_eq!(
+            rain_document.compose_declared(None),
+            Err(ComposeError::Reject(
+                "no declared entrypoints found in front matter or binding annotations".to_owned()
+            ))
+        );
+    }
+
     proptest! {
         #![proptest_config(Config {
             cases: 999,
@@ -1495,10 +2698,10 @@ _: opcode-1(0xabcd 456);
         })]
         #[test]
         fn test_fuzz_num_literals_compose(
-            a in [0f64..f64::MAX],
-            b in [0f64..f64::MAX],
-            c in [0f64..f64::MAX],
-            d in [0f64..f64::MAX],
+            a in [0f64..1e77],
+            b in [0f64..1e77],
+            c in [0f64..1e77],
+            d in [0f64..1e77],
         ) {
             let e1 = format!("{:e}", c[0]);
             let e2 = format!("{:e}", d[0]);
@@ -1516,7 +2719,7 @@ matter
 #exp-binding
 _: opcode-1<{} literal-a>(literal-b {});", a[0], b[0], e1, e2);
 
-            let rainlang_text = RainDocument::compose_text(&dotrain_text, &["exp-binding"], None, None).unwrap();
+            let rainlang_text = RainDocument::compose_text(&dotrain_text, &["exp-binding"], None, None, None).unwrap();
             let expected_rainlang = format!("/* 0. exp-binding */ \n_: opcode-1<{} {}>({} {});", e1, a[0], b[0], e2);
 
             assert_eq!(rainlang_text, expected_rainlang);
@@ -0,0 +1,212 @@
+use serde::{Serialize, Deserialize};
+use super::{line_starts, ComposedRainlang, super::parser::raindocument::RainDocument};
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Base64 characters used for VLQ encoding the mappings
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Type of a Source Map Revision 3 of a composed rainlang text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct SourceMapV3 {
+    pub version: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub file: Option<String>,
+    /// the root dotrain source followed by every imported dotrain by its URI or hash
+    pub sources: Vec<String>,
+    pub sources_content: Vec<Option<String>>,
+    pub names: Vec<String>,
+    /// base64 VLQ encoded mappings
+    pub mappings: String,
+}
+
+impl RainDocument {
+    /// builds a Source Map Revision 3 for the given composed rainlang of this instance,
+    /// `source` is the name (path) of this instance that is used as the first source and
+    /// imported dotrains are listed by their URI if known to the meta store or else by their hash
+    pub fn build_source_map(
+        &self,
+        composed: &ComposedRainlang,
+        file: Option<String>,
+        source: &str,
+    ) -> SourceMapV3 {
+        let mut sources = vec![source.to_owned()];
+        let mut sources_content = vec![Some(self.text.clone())];
+        let mut hashes = vec![String::new()];
+
+        let generated_lines = line_starts(&composed.text);
+        let mut original_lines = vec![line_starts(&self.text)];
+
+        // absolute segments of each generated line as [column, source, line, column]
+        let mut lines: Vec<Vec<[usize; 4]>> = vec![vec![]; generated_lines.len()];
+        for mapping in &composed.mappings {
+            let source_index = if let Some(index) = hashes.iter().position(|v| *v == mapping.hash) {
+                index
+            } else if let Some(dotrain) = self.import_document(&mapping.hash) {
+                sources.push(
+                    self.import_uri(&mapping.hash)
+                        .unwrap_or(mapping.hash.clone()),
+                );
+                sources_content.push(Some(dotrain.text.clone()));
+                hashes.push(mapping.hash.clone());
+                original_lines.push(line_starts(&dotrain.text));
+                hashes.len() - 1
+            } else {
+                continue;
+            };
+
+            // unedited fragments spanning multiple lines get a segment at each line start
+            let is_unedited = mapping.generated[1] - mapping.generated[0]
+                == mapping.original[1] - mapping.original[0];
+            let mut offsets = vec![(mapping.generated[0], mapping.original[0])];
+            if is_unedited {
+                offsets.extend(
+                    composed.text[mapping.generated[0]..mapping.generated[1]]
+                        .match_indices('\n')
+                        .map(|(i, _)| i + 1)
+                        .filter(|i| mapping.generated[0] + i < mapping.generated[1])
+                        .map(|i| (mapping.generated[0] + i, mapping.original[0] + i)),
+                );
+            }
+            for (generated, original) in offsets {
+                let (generated_line, generated_column) = line_column(&generated_lines, generated);
+                let (original_line, original_column) =
+                    line_column(&original_lines[source_index], original);
+                lines[generated_line].push([
+                    generated_column,
+                    source_index,
+                    original_line,
+                    original_column,
+                ]);
+            }
+        }
+
+        SourceMapV3 {
+            version: 3,
+            file,
+            sources,
+            sources_content,
+            names: vec![],
+            mappings: encode_mappings(&lines),
+        }
+    }
+}
+
+/// Returns the line and column of the given offset from a text's line starts
+fn line_column(lines: &[usize], offset: usize) -> (usize, usize) {
+    let line = lines.partition_point(|v| *v <= offset).saturating_sub(1);
+    (line, offset - lines[line])
+}
+
+/// Encodes absolute segments into Source Map Revision 3 mappings, generated column
+/// is relative to the previous segment of the same line while the rest are relative
+/// to the previous segment of the whole mappings
+fn encode_mappings(lines: &[Vec<[usize; 4]>]) -> String {
+    let mut previous = [0i64; 4];
+    lines
+        .iter()
+        .map(|segments| {
+            previous[0] = 0;
+            segments
+                .iter()
+                .map(|segment| {
+                    let mut encoded = String::new();
+                    for (i, value) in segment.iter().enumerate() {
+                        encode_vlq(*value as i64 - previous[i], &mut encoded);
+                        previous[i] = *value as i64;
+                    }
+                    encoded
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Encodes a value as base64 VLQ
+fn encode_vlq(value: i64, output: &mut String) {
+    let mut vlq = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = vlq & 0b11111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        output.push(BASE64_CHARS[digit as usize] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, RwLock};
+
+    #[test]
+    fn test_encode_vlq() {
+        let mut encoded = String::new();
+        for value in [0, 1, -1, 15, 16, -16, 1000] {
+            encode_vlq(value, &mut encoded);
+        }
+        assert_eq!(encoded, "ACDegBhBw+B");
+    }
+
+    #[test]
+    fn test_build_source_map() {
+        let mut store = rain_metadata::Store::new();
+        let import_text = r"---
+#value 4e18
+#exp
+_: opcode-2(value 1);
+";
+        let (import_hash, _) = store
+            .set_dotrain(import_text, "./import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let meta_store = Arc::new(RwLock::new(store));
+
+        let dotrain_text = format!(
+            r"---
+@imp {}
+#main
+_: opcode-1<'imp.exp>(1 2);
+",
+            import_hash
+        );
+        let rain_document =
+            RainDocument::create(dotrain_text.clone(), Some(meta_store), None, None);
+        let composed = rain_document
+            .compose_with_sourcemap(&["main"], None)
+            .unwrap();
+        let source_map =
+            rain_document.build_source_map(&composed, Some("main.rl".to_owned()), "main.rain");
+
+        assert_eq!(source_map.version, 3);
+        assert_eq!(source_map.file, Some("main.rl".to_owned()));
+        assert_eq!(source_map.sources, vec!["main.rain", "./import.rain"]);
+        assert_eq!(
+            source_map.sources_content,
+            vec![Some(dotrain_text), Some(import_text.to_owned())]
+        );
+
+        // each generated line of the composed text gets its own group of segments
+        let lines = source_map.mappings.split(';').collect::<Vec<_>>();
+        assert_eq!(lines.len(), composed.text.split('\n').count());
+
+        // name comment maps to binding name at line 2 column 1, the dependency index to its quote
+        // operand at column 12, and the imported binding comes from the second source
+        let expected_mappings = "AAEC;AACD,YAAY,CAAQ;;ACDnB;AACD,YAAY,IAAK";
+        assert_eq!(source_map.mappings, expected_mappings);
+    }
+}
@@ -1,4 +1,4 @@
-use super::types::ast::{Offsets, Problem};
+use super::types::ast::{Offsets, Problem, Severity};
 use serde_repr::{Serialize_repr, Deserialize_repr};
 
 #[cfg(feature = "js-api")]
@@ -47,6 +47,10 @@ pub enum ErrorCode {
     InvalidOperandArg = 0x211,
     InvalidSuppliedRebindings = 0x212,
     InvalidReferenceAll = 0x213,
+    InvalidFrontMatter = 0x214,
+    InvalidFrontMatterValue = 0x215,
+    InvalidOperandArgKind = 0x216,
+    InvalidEscapeSequence = 0x217,
 
     UnexpectedToken = 0x301,
     UnexpectedClosingParen = 0x302,
@@ -77,11 +81,18 @@ pub enum ErrorCode {
     OutOfRangeInputs = 0x601,
     OutOfRangeOperandArgs = 0x602,
     OutOfRangeValue = 0x603,
+    OutOfRangePrecision = 0x604,
+    OutOfRangeStringLength = 0x605,
 
     DuplicateAlias = 0x701,
     DuplicateIdentifier = 0x702,
     DuplicateImportStatement = 0x703,
     DuplicateImport = 0x704,
+
+    UnusedBinding = 0x801,
+    UnusedImport = 0x802,
+    UnusedAlias = 0x803,
+    ShadowedName = 0x804,
 }
 
 impl ErrorCode {
@@ -127,6 +138,10 @@ impl ErrorCode {
             Self::InvalidNamespaceReference => format!("expected a node, {} is a namespace", msg_items[0]),
             Self::InvalidSuppliedRebindings => format!("invalid supplied rebinding: {}", msg_items[0]),
             Self::InvalidReferenceAll => format!("invalid reference to binding: {}, only literal or quote bindings can be referenced", msg_items[0]),
+            Self::InvalidFrontMatter => format!("invalid front matter: {}", msg_items[0]),
+            Self::InvalidFrontMatterValue => format!("invalid front matter value at {}: {}", msg_items[0], msg_items[1]),
+            Self::InvalidOperandArgKind => format!("invalid operand arg: {}, expected a {} for {}", msg_items[0], msg_items[1], msg_items[2]),
+            Self::InvalidEscapeSequence => "invalid escape sequence in string literal".to_owned(),
 
             Self::UnexpectedToken => "unexpected token".to_owned(),
             Self::UnexpectedClosingParen => "unexpected \")\"".to_owned(),
@@ -150,23 +165,35 @@ impl ErrorCode {
             Self::ExpectedRename => "expected to be renamed".to_owned(),
             Self::ExpectedLiteral => "expected to be followed by a literal".to_owned(),
 
-            Self::MismatchRHS => String::new(),
-            Self::MismatchLHS => String::new(),
-            Self::MismatchOperandArgs => String::new(),
+            Self::MismatchRHS => format!("expected {} RHS outputs to match LHS items, found {}", msg_items[0], msg_items[1]),
+            Self::MismatchLHS => format!("expected {} LHS items to match RHS outputs, found {}", msg_items[0], msg_items[1]),
+            Self::MismatchOperandArgs => format!("expected {} operand args for {}, found {}", msg_items[0], msg_items[1], msg_items[2]),
 
-            Self::OutOfRangeInputs => String::new(),
-            Self::OutOfRangeOperandArgs => String::new(),
+            Self::OutOfRangeInputs => format!("expected {} inputs for {}, found {}", msg_items[0], msg_items[1], msg_items[2]),
+            Self::OutOfRangeOperandArgs => format!("operand arg {} out of range, expected {}", msg_items[0], msg_items[1]),
             Self::OutOfRangeValue => "value out of range".to_owned(),
+            Self::OutOfRangePrecision => "value cannot be represented without precision loss".to_owned(),
+            Self::OutOfRangeStringLength => "string literal longer than 31 bytes".to_owned(),
 
             Self::DuplicateAlias => format!("duplicate alias: {}", msg_items[0]),
             Self::DuplicateIdentifier => "duplicate identifier".to_owned(),
             Self::DuplicateImportStatement => "duplicate import statement".to_owned(),
             Self::DuplicateImport => "duplicate import".to_owned(),
+
+            Self::UnusedBinding => format!("unused binding: {}", msg_items[0]),
+            Self::UnusedImport => format!("unused import: {}", msg_items[0]),
+            Self::UnusedAlias => format!("unused alias: {}", msg_items[0]),
+            Self::ShadowedName => format!(
+                "alias {} shadows the binding or namespace of the same name",
+                msg_items[0]
+            ),
         };
         Problem {
             msg,
             position,
             code: self,
+            severity: Severity::Error,
+            origin: None,
         }
     }
 }
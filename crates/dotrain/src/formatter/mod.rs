@@ -0,0 +1,313 @@
+//! Canonical formatter of RainDocument texts built on top of their lossless concrete syntax tree
+//!
+//! Formatting only ever changes whitespaces, every other character (including comments) is kept
+//! in the same order, front matter and literal, elided and quote bindings bodies are kept as they are:
+//! - import statements are placed one per line with single spaces between their items
+//! - bindings are separated by a blank line, literal, elided and quote bindings bodies are placed
+//!   after a single space following the binding name and rainlang bindings bodies on the next lines
+//! - each rainlang line is placed on its own line as `LHS: RHS` ending with its `,` or `;`
+//! - operand args and inputs of opcodes get no padding inside their `<...>` and `(...)`
+//! - comments that are not inside of an item are placed on their own lines
+
+use super::{
+    parser::{dotrain_cst, raindocument::RainDocument},
+    types::cst::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken},
+};
+
+impl RainDocument {
+    /// Formats this instance's text
+    pub fn format(&self) -> String {
+        format_dotrain(&self.text)
+    }
+}
+
+/// Formats the given RainDocument text canonically
+pub fn format_dotrain(text: &str) -> String {
+    let cst = dotrain_cst(text);
+    let mut output = String::new();
+    let mut blocks: Vec<Vec<String>> = vec![];
+    let mut pending: Vec<String> = vec![];
+    let mut last_kind = None;
+    let mut last_end = None;
+    for child in &cst.children {
+        let end = last_end.replace(child.position()[1]);
+        match child {
+            SyntaxElement::Node(node) if node.kind == SyntaxKind::FrontMatter => {
+                output.push_str(&node.to_string());
+                output.push('\n');
+            }
+            SyntaxElement::Node(node) => {
+                // consecutive imports are kept in one block, comments right before
+                // an item belong to its block
+                let mut lines = std::mem::take(&mut pending);
+                if node.kind == SyntaxKind::Import {
+                    lines.push(inline(&node.tokens()));
+                } else {
+                    lines.extend(binding(node));
+                }
+                match blocks.last_mut() {
+                    Some(block)
+                        if node.kind == SyntaxKind::Import
+                            && last_kind == Some(SyntaxKind::Import) =>
+                    {
+                        block.extend(lines)
+                    }
+                    _ => blocks.push(lines),
+                }
+                last_kind = Some(node.kind);
+            }
+            SyntaxElement::Token(token) if token.kind == SyntaxKind::Whitespace => {
+                last_end = None;
+            }
+            SyntaxElement::Token(token) => {
+                // adjacent tokens are kept adjacent
+                match pending.last_mut() {
+                    Some(line) if end == Some(token.position[0]) => line.push_str(&token.text),
+                    _ => pending.push(token.text.clone()),
+                }
+                last_kind = Some(token.kind);
+            }
+        }
+    }
+    if !pending.is_empty() {
+        blocks.push(pending);
+    }
+    let body = blocks
+        .iter()
+        .map(|v| v.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    if !body.is_empty() {
+        output.push_str(&body);
+        // an unended comment spans to the end of the text
+        if !cst
+            .tokens()
+            .last()
+            .is_some_and(|v| v.kind == SyntaxKind::Comment && !v.text.ends_with("*/"))
+        {
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Formats a binding node into lines
+fn binding(node: &SyntaxNode) -> Vec<String> {
+    let Some(name) = node.child_token(SyntaxKind::Name) else {
+        return vec![node.to_string()];
+    };
+    let mut header = format!("#{}", name.text);
+    let mut lines = vec![];
+    for child in node.children.iter().skip(2) {
+        match child {
+            SyntaxElement::Token(token) if token.kind == SyntaxKind::Comment => {
+                header.push(' ');
+                header.push_str(&token.text);
+            }
+            SyntaxElement::Token(_) => {}
+            SyntaxElement::Node(body) if body.kind == SyntaxKind::Rainlang => {
+                lines.extend(rainlang(body));
+            }
+            SyntaxElement::Node(body) => {
+                header.push(' ');
+                header.push_str(&body.to_string());
+            }
+        }
+    }
+    lines.insert(0, header);
+    lines
+}
+
+/// Formats a rainlang node into lines
+fn rainlang(node: &SyntaxNode) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for child in &node.children {
+        match child {
+            SyntaxElement::Node(source) if source.kind == SyntaxKind::Source => {
+                for item in &source.children {
+                    match item {
+                        SyntaxElement::Node(line) => lines.push(inline(&line.tokens())),
+                        SyntaxElement::Token(token)
+                            if token.kind == SyntaxKind::Semi && !lines.is_empty() =>
+                        {
+                            lines.last_mut().unwrap().push(';')
+                        }
+                        SyntaxElement::Token(token) if token.kind != SyntaxKind::Whitespace => {
+                            lines.push(token.text.clone())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SyntaxElement::Node(pragma) => lines.push(inline(&pragma.tokens())),
+            SyntaxElement::Token(token) if token.kind != SyntaxKind::Whitespace => {
+                lines.push(token.text.clone())
+            }
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Formats the given tokens into a single line, whitespaces become a single space or are
+/// removed where they are not needed, items that are adjacent are kept adjacent
+fn inline(tokens: &[&SyntaxToken]) -> String {
+    let mut line = String::new();
+    let mut previous: Option<&SyntaxToken> = None;
+    for token in tokens.iter().filter(|v| v.kind != SyntaxKind::Whitespace) {
+        if let Some(previous) = previous {
+            let is_adjacent = previous.position[1] == token.position[0];
+            let is_tight = matches!(
+                previous.kind,
+                SyntaxKind::At | SyntaxKind::LParen | SyntaxKind::LAngle
+            ) || matches!(
+                token.kind,
+                SyntaxKind::Colon
+                    | SyntaxKind::Comma
+                    | SyntaxKind::Semi
+                    | SyntaxKind::RParen
+                    | SyntaxKind::RAngle
+            );
+            if previous.kind == SyntaxKind::Colon || (!is_adjacent && !is_tight) {
+                line.push(' ');
+            }
+        }
+        line.push_str(&token.text);
+        previous = Some(token);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_format_dotrain() {
+        let text = r#"some: front-matter
+---
+@  ns   0x1234
+    'a 'b
+@other 0x5678
+
+
+/* main entrypoint */
+#main
+  _  _ :add( 1   2 )
+     mul<1  2>(x "a  b") ,
+_:opcode<  1 >( ) ;   #literal   4e18
+/* some comment */ #elided    ! not  here
+#quote 'ns.exp
+#pragma using-words-from 0x1234 _: add(1 2);"#;
+        let expected = r#"some: front-matter
+---
+@ns 0x1234 'a 'b
+@other 0x5678
+
+/* main entrypoint */
+#main
+_ _: add(1 2) mul<1 2>(x "a  b"),
+_: opcode<1>();
+
+#literal 4e18
+
+/* some comment */
+#elided ! not  here
+
+#quote 'ns.exp
+
+#pragma
+using-words-from 0x1234
+_: add(1 2);
+"#;
+        let formatted = format_dotrain(text);
+        assert_eq!(formatted, expected);
+        assert_eq!(format_dotrain(&formatted), expected);
+
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        assert_eq!(rain_document.format(), expected);
+        let formatted_document = RainDocument::create(formatted, None, None, None);
+        assert_eq!(
+            formatted_document
+                .all_problems()
+                .iter()
+                .map(|v| &v.code)
+                .collect::<Vec<_>>(),
+            rain_document
+                .all_problems()
+                .iter()
+                .map(|v| &v.code)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    /// well formed dotrain texts with arbitrary whitespaces and comments
+    fn dotrain_text() -> impl Strategy<Value = String> {
+        let ws = prop_oneof![Just(" "), Just("\n"), Just("  \n  "), Just("\t")];
+        let ws0 = prop_oneof![Just(""), Just(" "), Just("\n ")];
+        let comment = prop_oneof![
+            Just(""),
+            Just("/* c */\n"),
+            Just("/* é ✓ */\n"),
+            Just("/* @entrypoint */ ")
+        ];
+        let binding = (
+            0usize..4,
+            ws.clone(),
+            ws.clone(),
+            ws,
+            ws0.clone(),
+            ws0,
+            comment.clone(),
+            comment,
+        );
+        prop::collection::vec(binding, 1..5).prop_map(|bindings| {
+            let mut text = String::from("---");
+            for (i, (typ, w1, w2, w3, z1, z2, c1, c2)) in bindings.into_iter().enumerate() {
+                let body = match typ {
+                    0 => "4e18".to_owned(),
+                    1 => "\"a  b\"".to_owned(),
+                    2 => format!("! some{w1}msg"),
+                    _ => format!(
+                        "{c2}_{w1}_{z1}:{w2}add({z1}1{w1}2{z2}){w2}mul<1{w1}2>(3{w2}b0){z2},{w1}{c2}:{w2}b0;{z2}"
+                    ),
+                };
+                text.push_str(&format!("{w3}{c1}#b{i}{w1}{body}"));
+            }
+            text
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_format_only_changes_whitespaces(
+            text in "[ab0x1/*@#;,:()<>\"!'. \n-∂]{0,80}"
+        ) {
+            let formatted = format_dotrain(&text);
+            prop_assert_eq!(
+                formatted.split_whitespace().collect::<String>(),
+                text.split_whitespace().collect::<String>()
+            );
+        }
+
+        #[test]
+        fn test_format_keeps_problems_and_entrypoints(text in dotrain_text()) {
+            let formatted = format_dotrain(&text);
+            prop_assert_eq!(format_dotrain(&formatted), formatted.clone());
+
+            let rain_document = RainDocument::create(text, None, None, None);
+            let formatted_document = RainDocument::create(formatted, None, None, None);
+            let codes = |v: &RainDocument| {
+                let mut codes = v.all_problems().iter().map(|v| v.code as usize).collect::<Vec<_>>();
+                codes.sort();
+                codes
+            };
+            prop_assert_eq!(codes(&rain_document), codes(&formatted_document));
+            prop_assert_eq!(
+                rain_document.declared_entrypoints(),
+                formatted_document.declared_entrypoints()
+            );
+        }
+    }
+}
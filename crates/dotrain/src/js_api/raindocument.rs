@@ -3,12 +3,24 @@ use super::{
     store::MetaStore,
     Namespace, IRainDocument, IAuthoringMeta,
     super::{
-        parser::raindocument::{RainDocument, Rebind},
+        parser::{
+            raindocument::{RainDocument, Rebind, TextEdit},
+            frontmatter::FrontMatterSchema,
+        },
+        linter::LintConfig,
         error::ComposeError,
-        types::ast::{Problem, Import, Comment, Binding},
+        composer::{
+            ComposedRainlang, ComposedExpression, ComposedMeta, ComposeOptions, SourceMapV3,
+        },
+        types::{
+            ast::{Problem, Import, Comment, Binding},
+            cst::SyntaxNode,
+            words::WordSpecs,
+        },
     },
 };
-use serde_wasm_bindgen::{Error, to_value, from_value};
+use serde::Serialize;
+use serde_wasm_bindgen::{Error, to_value, from_value, Serializer};
 use wasm_bindgen::{
     JsValue,
     convert::*,
@@ -66,6 +78,17 @@ impl RainDocument {
         self.update(new_text.to_string(), rebinds)
     }
 
+    /// Applies the edits to the text and reparses right away reusing the unchanged items
+    #[wasm_bindgen(js_name = "applyEdits")]
+    pub fn js_apply_edits(
+        &mut self,
+        edits: Vec<TextEdit>,
+        rebinds: Option<Vec<Rebind>>,
+    ) -> Result<(), JsValue> {
+        self.apply_edits(edits, rebinds)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Updates the text and parses right away with remote meta search enabled
     #[wasm_bindgen(js_name = "updateAsync")]
     pub async fn js_update_async(&mut self, new_text: &str, rebinds: Option<Vec<Rebind>>) {
@@ -84,6 +107,59 @@ impl RainDocument {
         self.front_matter().to_string()
     }
 
+    /// This instance's front matter parsed as YAML (or as JSON if it starts with `{` or `[`),
+    /// undefined if the front matter is empty or is not valid
+    #[wasm_bindgen(getter, js_name = "frontMatterValue")]
+    pub fn js_front_matter_value(&self) -> JsValue {
+        self.front_matter_value()
+            .and_then(|v| v.serialize(&Serializer::json_compatible()).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// This instance's front matter schema
+    #[wasm_bindgen(getter, js_name = "frontMatterSchema")]
+    pub fn js_front_matter_schema(&self) -> Option<FrontMatterSchema> {
+        self.front_matter_schema.clone()
+    }
+
+    /// Sets the schema that the front matter is validated against and revalidates the front
+    /// matter right away without reparsing
+    #[wasm_bindgen(js_name = "setFrontMatterSchema")]
+    pub fn js_set_front_matter_schema(&mut self, schema: Option<FrontMatterSchema>) {
+        self.set_front_matter_schema(schema)
+    }
+
+    /// This instance's lint config
+    #[wasm_bindgen(getter, js_name = "lintConfig")]
+    pub fn js_lint_config(&self) -> LintConfig {
+        self.lint_config.clone()
+    }
+
+    /// Sets the levels of the lints and reruns the lints right away without reparsing
+    #[wasm_bindgen(js_name = "setLintConfig")]
+    pub fn js_set_lint_config(&mut self, lint_config: LintConfig) {
+        self.set_lint_config(lint_config)
+    }
+
+    /// This instance's word specs
+    #[wasm_bindgen(getter, js_name = "wordSpecs")]
+    pub fn js_word_specs(&self) -> WordSpecs {
+        self.word_specs.clone()
+    }
+
+    /// Sets the specs of words that the opcodes of the rainlang bindings are checked against
+    /// and rechecks them right away without reparsing
+    #[wasm_bindgen(js_name = "setWordSpecs")]
+    pub fn js_set_word_specs(&mut self, word_specs: WordSpecs) {
+        self.set_word_specs(word_specs)
+    }
+
+    /// Runs all the lints over this instance and returns their problems
+    #[wasm_bindgen(js_name = "lint")]
+    pub fn js_lint(&self) -> Vec<Problem> {
+        self.lint()
+    }
+
     /// This instance's current text
     #[wasm_bindgen(getter, js_name = "body")]
     pub fn js_body(&self) -> String {
@@ -102,6 +178,12 @@ impl RainDocument {
         self.comments.clone()
     }
 
+    /// This instance's lossless concrete syntax tree
+    #[wasm_bindgen(js_name = "cst")]
+    pub fn js_cst(&self) -> SyntaxNode {
+        self.cst()
+    }
+
     /// This instance's imports
     #[wasm_bindgen(getter, js_name = "imports")]
     pub fn js_imports(&self) -> Vec<Import> {
@@ -172,17 +254,121 @@ impl RainDocument {
         block_on(self.parse(false, rebinds));
     }
 
+    /// Returns a copy of this instance with the given rebinds applied without reparsing its text
+    #[wasm_bindgen(js_name = "withRebinds")]
+    pub fn js_with_rebinds(&self, rebinds: Vec<Rebind>) -> RainDocument {
+        self.with_rebinds(rebinds)
+    }
+
     /// Composes this instance
     #[wasm_bindgen(js_name = "compose")]
-    pub async fn js_compose(&self, entrypoints: Vec<String>) -> Result<String, ComposeError> {
+    pub async fn js_compose(
+        &self,
+        entrypoints: Vec<String>,
+        options: Option<ComposeOptions>,
+    ) -> Result<String, ComposeError> {
         self.compose(
             &entrypoints
                 .iter()
                 .map(|v| v.as_str())
                 .collect::<Vec<&str>>(),
+            options,
         )
     }
 
+    /// Entrypoints declared by this instance's front matter or binding annotations
+    #[wasm_bindgen(js_name = "declaredEntrypoints")]
+    pub fn js_declared_entrypoints(&self) -> Vec<String> {
+        self.declared_entrypoints()
+    }
+
+    /// Bindings of this instance that are not reachable from the given entrypoints
+    #[wasm_bindgen(js_name = "unusedBindings")]
+    pub fn js_unused_bindings(&self, entrypoints: Vec<String>) -> Vec<Binding> {
+        self.unused_bindings(&entrypoints.iter().map(|v| v.as_str()).collect::<Vec<_>>())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Imports of this instance that none of the given entrypoints and their dependencies reference
+    #[wasm_bindgen(js_name = "unusedImports")]
+    pub fn js_unused_imports(&self, entrypoints: Vec<String>) -> Vec<Import> {
+        self.unused_imports(&entrypoints.iter().map(|v| v.as_str()).collect::<Vec<_>>())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Composes this instance from its declared entrypoints
+    #[wasm_bindgen(js_name = "composeDeclared")]
+    pub fn js_compose_declared(
+        &self,
+        options: Option<ComposeOptions>,
+    ) -> Result<String, ComposeError> {
+        self.compose_declared(options)
+    }
+
+    /// Composes this instance into a structured expression
+    #[wasm_bindgen(js_name = "composeExpression")]
+    pub fn js_compose_expression(
+        &self,
+        entrypoints: Vec<String>,
+        options: Option<ComposeOptions>,
+    ) -> Result<ComposedExpression, ComposeError> {
+        self.compose_expression(
+            &entrypoints
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<&str>>(),
+            options,
+        )
+    }
+
+    /// Composes this instance and packs it along with the composed text into a cbor encoded
+    /// Rain meta document
+    #[wasm_bindgen(js_name = "composeMeta")]
+    pub fn js_compose_meta(
+        &self,
+        entrypoints: Vec<String>,
+        options: Option<ComposeOptions>,
+    ) -> Result<ComposedMeta, ComposeError> {
+        self.compose_meta(
+            &entrypoints
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<&str>>(),
+            options,
+        )
+    }
+
+    /// Composes this instance along with the sourcemap of the composed text
+    #[wasm_bindgen(js_name = "composeWithSourcemap")]
+    pub fn js_compose_with_sourcemap(
+        &self,
+        entrypoints: Vec<String>,
+        options: Option<ComposeOptions>,
+    ) -> Result<ComposedRainlang, ComposeError> {
+        self.compose_with_sourcemap(
+            &entrypoints
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<&str>>(),
+            options,
+        )
+    }
+
+    /// Builds a Source Map (revision 3) for the given composed rainlang of this instance
+    #[wasm_bindgen(js_name = "buildSourceMap")]
+    pub fn js_build_source_map(
+        &self,
+        composed: ComposedRainlang,
+        file: Option<String>,
+        source: &str,
+    ) -> SourceMapV3 {
+        self.build_source_map(&composed, file, source)
+    }
+
     /// Composes a text as RainDocument with remote meta search enabled for parsing
     #[wasm_bindgen(js_name = "composeTextAsync")]
     pub async fn js_compose_text_async(
@@ -190,6 +376,7 @@ impl RainDocument {
         entrypoints: Vec<String>,
         meta_store: &MetaStore,
         rebinds: Option<Vec<Rebind>>,
+        options: Option<ComposeOptions>,
     ) -> Result<String, ComposeError> {
         RainDocument::compose_text_async(
             text,
@@ -199,6 +386,7 @@ impl RainDocument {
                 .collect::<Vec<&str>>(),
             Some(meta_store.0.clone()),
             rebinds,
+            options,
         )
         .await
     }
@@ -210,6 +398,7 @@ impl RainDocument {
         entrypoints: Vec<String>,
         meta_store: &MetaStore,
         rebinds: Option<Vec<Rebind>>,
+        options: Option<ComposeOptions>,
     ) -> Result<String, ComposeError> {
         RainDocument::compose_text(
             text,
@@ -219,6 +408,7 @@ impl RainDocument {
                 .collect::<Vec<&str>>(),
             Some(meta_store.0.clone()),
             rebinds,
+            options,
         )
     }
 }
@@ -252,3 +442,33 @@ impl WasmDescribeVector for Rebind {
         Rebind::describe();
     }
 }
+
+impl VectorIntoWasmAbi for TextEdit {
+    type Abi = <Box<[JsValue]> as IntoWasmAbi>::Abi;
+    fn vector_into_abi(vector: Box<[Self]>) -> Self::Abi {
+        js_value_vector_into_abi(vector)
+    }
+}
+impl From<TextEdit> for JsValue {
+    fn from(value: TextEdit) -> Self {
+        to_value(&value).unwrap_throw()
+    }
+}
+impl TryFromJsValue for TextEdit {
+    type Error = Error;
+    fn try_from_js_value(value: JsValue) -> Result<Self, Self::Error> {
+        from_value(value)
+    }
+}
+impl VectorFromWasmAbi for TextEdit {
+    type Abi = <Box<[JsValue]> as IntoWasmAbi>::Abi;
+    unsafe fn vector_from_abi(js: Self::Abi) -> Box<[Self]> {
+        js_value_vector_from_abi(js)
+    }
+}
+impl WasmDescribeVector for TextEdit {
+    fn describe_vector() {
+        inform(VECTOR);
+        TextEdit::describe();
+    }
+}
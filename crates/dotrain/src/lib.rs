@@ -25,6 +25,8 @@ pub mod types;
 pub mod error;
 pub(crate) mod parser;
 pub(crate) mod composer;
+pub(crate) mod formatter;
+pub(crate) mod linter;
 
 #[cfg(feature = "cli")]
 pub mod cli;
@@ -34,5 +36,14 @@ pub mod js_api;
 
 pub use rain_metadata::Store;
 pub use parser::*;
+pub use composer::*;
+pub use formatter::*;
+pub use linter::*;
 /// Provides all types and functionalities of Rain metadata
 pub use rain_metadata;
+
+/// Parser internals used by the benchmarks, not part of the public API
+#[doc(hidden)]
+pub mod __bench {
+    pub use crate::parser::cst::split_dotrain;
+}
@@ -0,0 +1,630 @@
+//! Lint passes over RainDocuments that report code smells as problems without failing the compose
+//!
+//! Each lint has a stable name and error code, its problems are reported with warning severity by
+//! default and its level can be configured with a [LintConfig] (the `lints` field of rainconfig) or
+//! with `@allow`, `@warn` and `@deny` annotations followed by lint names in comments, for example
+//! `/* @allow unused-alias shadowed-name */`:
+//! - an annotation comment right before a binding (only `#` between them) or inside of a binding
+//!   applies to that binding only, any other annotation comment applies to the whole document
+//! - binding annotations take precedence over document annotations which take precedence over
+//!   the config
+//! - denied lints are reported with error severity which makes the RainDocument uncomposable
+
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use super::{
+    error::ErrorCode,
+    parser::raindocument::RainDocument,
+    types::ast::{Binding, BindingItem, Import, NamespaceItem, Node, Offsets, Problem, Severity},
+};
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Lints that can be run over a RainDocument
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum Lint {
+    /// A binding that is not reachable from any of the declared entrypoints, only checked
+    /// when the document declares its entrypoints
+    UnusedBinding,
+    /// An import that none of the declared entrypoints and their dependencies reference,
+    /// only checked when the document declares its entrypoints
+    UnusedImport,
+    /// A LHS alias that is not referenced by any of the next lines of its source, allowed
+    /// by default as every LHS item is an output of its source
+    UnusedAlias,
+    /// A LHS alias that has the same name as a binding or a namespace
+    ShadowedName,
+}
+
+/// Level of a lint
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum LintLevel {
+    /// the lint is not reported
+    Allow,
+    /// the lint is reported with warning severity
+    Warn,
+    /// the lint is reported with error severity
+    Deny,
+}
+
+/// Levels of lints keyed by their name, lints that are not present have their default level
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct LintConfig(pub HashMap<Lint, LintLevel>);
+
+impl Lint {
+    /// All lints in the order they are run
+    pub const ALL: [Lint; 4] = [
+        Lint::UnusedBinding,
+        Lint::UnusedImport,
+        Lint::UnusedAlias,
+        Lint::ShadowedName,
+    ];
+
+    /// Stable name of this lint used in rainconfig and comment annotations
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedBinding => "unused-binding",
+            Lint::UnusedImport => "unused-import",
+            Lint::UnusedAlias => "unused-alias",
+            Lint::ShadowedName => "shadowed-name",
+        }
+    }
+
+    /// Finds the lint of the given name
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Lint::ALL.into_iter().find(|v| v.name() == name)
+    }
+
+    /// Stable error code of this lint's problems
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Lint::UnusedBinding => ErrorCode::UnusedBinding,
+            Lint::UnusedImport => ErrorCode::UnusedImport,
+            Lint::UnusedAlias => ErrorCode::UnusedAlias,
+            Lint::ShadowedName => ErrorCode::ShadowedName,
+        }
+    }
+
+    /// Finds the lint of the given error code
+    pub fn from_code(code: ErrorCode) -> Option<Lint> {
+        Lint::ALL.into_iter().find(|v| v.code() == code)
+    }
+
+    /// Level of this lint when it is not configured
+    pub fn default_level(&self) -> LintLevel {
+        match self {
+            Lint::UnusedAlias => LintLevel::Allow,
+            _ => LintLevel::Warn,
+        }
+    }
+
+    /// Runs this lint's pass over the given RainDocument and returns the message item
+    /// and position of each found problem
+    fn check(&self, rain_document: &RainDocument) -> Vec<(String, Offsets)> {
+        match self {
+            Lint::UnusedBinding | Lint::UnusedImport => {
+                let entrypoints = rain_document.declared_entrypoints();
+                if entrypoints.is_empty() {
+                    return vec![];
+                }
+                let entrypoints = entrypoints.iter().map(|v| v.as_str()).collect::<Vec<_>>();
+                if *self == Lint::UnusedBinding {
+                    rain_document
+                        .unused_bindings(&entrypoints)
+                        .into_iter()
+                        .map(|v| (v.name.clone(), v.name_position))
+                        .collect()
+                } else {
+                    rain_document
+                        .unused_imports(&entrypoints)
+                        .into_iter()
+                        .map(|import| {
+                            let name = if import.name == "." {
+                                &import.hash
+                            } else {
+                                &import.name
+                            };
+                            // the import statement without the whitespaces and comments after it
+                            let end = import
+                                .configuration
+                                .iter()
+                                .flat_map(|v| &v.groups)
+                                .flat_map(|(key, value)| [Some(key), value.as_ref()])
+                                .flatten()
+                                .map(|v| v.1[1])
+                                .fold(import.hash_position[1], usize::max);
+                            (name.clone(), [import.position[0], end])
+                        })
+                        .collect()
+                }
+            }
+            Lint::UnusedAlias | Lint::ShadowedName => {
+                let mut found = vec![];
+                for binding in &rain_document.bindings {
+                    let BindingItem::Exp(rainlang_doc) = &binding.item else {
+                        continue;
+                    };
+                    let offset = binding.content_position[0];
+                    for source in &rainlang_doc.ast {
+                        for (i, line) in source.lines.iter().enumerate() {
+                            for alias in line.aliases.iter().filter(|v| v.name != "_") {
+                                let is_found = if *self == Lint::UnusedAlias {
+                                    !source.lines[i + 1..].iter().any(|v| {
+                                        v.nodes.iter().any(|v| is_referencing(v, &alias.name))
+                                    })
+                                } else {
+                                    rain_document.namespace.contains_key(&alias.name)
+                                };
+                                if is_found {
+                                    found.push((
+                                        alias.name.clone(),
+                                        [alias.position[0] + offset, alias.position[1] + offset],
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+                found
+            }
+        }
+    }
+}
+
+impl LintLevel {
+    /// Severity of the problems of a lint with this level, None if they are not reported
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Deny => Some(Severity::Error),
+        }
+    }
+
+    /// Finds the level of the given comment annotation, i.e. `@allow`, `@warn` or `@deny`
+    pub fn from_annotation(annotation: &str) -> Option<LintLevel> {
+        match annotation {
+            "@allow" => Some(LintLevel::Allow),
+            "@warn" => Some(LintLevel::Warn),
+            "@deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl LintConfig {
+    /// Configured level of the given lint, its default level if it is not configured
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        self.0
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level())
+    }
+
+    /// Checks if no lint is configured
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl RainDocument {
+    /// This instance's lint config
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+
+    /// Sets the levels of the lints and reruns the lints right away without reparsing
+    pub fn set_lint_config(&mut self, lint_config: LintConfig) {
+        self.lint_config = lint_config;
+        self.problems.retain(|v| Lint::from_code(v.code).is_none());
+        self.apply_lints();
+    }
+
+    /// Runs all the lints over this instance and returns their problems with the severity of
+    /// their level resolved from the comment annotations and the lint config
+    pub fn lint(&self) -> Vec<Problem> {
+        let annotations = self.lint_annotations();
+        let mut problems = vec![];
+        for lint in Lint::ALL {
+            let annotations = annotations
+                .iter()
+                .rev()
+                .filter(|v| v.1 == lint)
+                .collect::<Vec<_>>();
+            let document_level = annotations
+                .iter()
+                .find(|v| v.0.is_none())
+                .map(|v| v.2)
+                .unwrap_or_else(|| self.lint_config.level(lint));
+            for (item, position) in lint.check(self) {
+                let level = annotations
+                    .iter()
+                    .find(|v| {
+                        v.0.is_some_and(|v| v[0] <= position[0] && position[0] < v[1])
+                    })
+                    .map_or(document_level, |v| v.2);
+                if let Some(severity) = level.severity() {
+                    let mut problem = lint.code().to_problem(vec![&item], position);
+                    problem.severity = severity;
+                    problems.push(problem);
+                }
+            }
+        }
+        problems
+    }
+
+    /// Bindings of this instance that are not reachable from the given entrypoints through
+    /// the bindings, quotes and namespace paths that the entrypoints and their dependencies
+    /// reference, elided bindings are not included as they are meant to be overridden
+    pub fn unused_bindings(&self, entrypoints: &[&str]) -> Vec<&Binding> {
+        let (bindings, _) = self.reachable_items(entrypoints);
+        self.bindings
+            .iter()
+            .filter(|v| {
+                !matches!(v.item, BindingItem::Elided(_)) && !bindings.contains(&v.name.as_str())
+            })
+            .collect()
+    }
+
+    /// Imports of this instance that none of the given entrypoints and their dependencies
+    /// reference, imports that could not be resolved or have problems are not included
+    pub fn unused_imports(&self, entrypoints: &[&str]) -> Vec<&Import> {
+        let (_, imports) = self.reachable_items(entrypoints);
+        self.imports
+            .iter()
+            .enumerate()
+            .filter(|(i, import)| {
+                import
+                    .sequence
+                    .as_ref()
+                    .is_some_and(|v| v.dotrain.is_some())
+                    && import.problems.is_empty()
+                    && !imports.contains(&(*i as isize))
+            })
+            .map(|(_, import)| import)
+            .collect()
+    }
+
+    /// Runs the lints and adds their problems to the top problems, imported dotrains are not linted
+    pub(crate) fn apply_lints(&mut self) {
+        if self.import_depth == 0 {
+            let problems = self.lint();
+            self.problems.extend(problems);
+        }
+    }
+
+    /// Reads the lint annotations of the comments in the order they appear as the position of
+    /// the binding they apply to (None for the whole document), the lint and its level
+    fn lint_annotations(&self) -> Vec<(Option<Offsets>, Lint, LintLevel)> {
+        let mut annotations = vec![];
+        for comment in &self.comments {
+            let scope = self
+                .bindings
+                .iter()
+                .find(|b| self.is_binding_annotation(comment, b))
+                .or_else(|| {
+                    self.bindings.iter().find(|b| {
+                        b.name_position[1] <= comment.position[0]
+                            && comment.position[1] <= self.binding_code_end(b)
+                    })
+                })
+                .map(|b| [b.name_position[0], self.binding_code_end(b)]);
+            let mut level = None;
+            for word in comment
+                .comment
+                .trim_start_matches("/*")
+                .trim_end_matches("*/")
+                .split_whitespace()
+            {
+                if word.starts_with('@') {
+                    level = LintLevel::from_annotation(word);
+                } else if let (Some(level), Some(lint)) =
+                    (level, Lint::from_name(word.trim_end_matches(',')))
+                {
+                    annotations.push((scope, lint, level));
+                }
+            }
+        }
+        annotations
+    }
+
+    /// Names of the own bindings and indexes of the imports that are reachable from the
+    /// given entrypoints, own bindings are followed through their references
+    fn reachable_items<'a>(&'a self, entrypoints: &[&'a str]) -> (Vec<&'a str>, Vec<isize>) {
+        let mut bindings = vec![];
+        let mut imports = vec![];
+        let mut references = entrypoints.to_vec();
+        while let Some(reference) = references.pop() {
+            let name = root_name(reference);
+            match self.namespace.get(name) {
+                Some(NamespaceItem::Leaf(leaf)) if leaf.import_index == -1 => {
+                    if let Some(binding) = self.bindings.iter().find(|v| v.name == name) {
+                        if !bindings.contains(&name) {
+                            bindings.push(binding.name.as_str());
+                            references.extend(binding_references(binding));
+                        }
+                    }
+                }
+                Some(item) => import_indexes(item, &mut imports),
+                None => {}
+            }
+        }
+        (bindings, imports)
+    }
+
+    /// End of the given binding's content without the comments that trail it, as they
+    /// are before the next binding or at the end of the document
+    fn binding_code_end(&self, binding: &Binding) -> usize {
+        let mut end = binding.content_position[1];
+        while let Some(comment) = self
+            .comments
+            .iter()
+            .find(|v| v.position[1] == end && binding.content_position[0] < v.position[0])
+        {
+            end = self.text[..comment.position[0]].trim_end().len();
+        }
+        end
+    }
+}
+
+/// Names of the bindings and namespaces that the given binding references as they appear in its text
+fn binding_references(binding: &Binding) -> Vec<&str> {
+    match &binding.item {
+        BindingItem::Exp(rainlang_doc) => {
+            let mut references = rainlang_doc
+                .dependencies
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<_>>();
+            for source in &rainlang_doc.ast {
+                let mut aliases = vec![];
+                for line in &source.lines {
+                    aliases.extend(line.aliases.iter().map(|v| v.name.as_str()));
+                    for node in &line.nodes {
+                        node_references(node, &aliases, &mut references);
+                    }
+                }
+            }
+            references
+        }
+        BindingItem::Quote(quote) => vec![quote.quote.as_str()],
+        _ => vec![],
+    }
+}
+
+/// Collects the references of the given node and its inputs that are not the given aliases
+fn node_references<'a>(node: &'a Node, aliases: &[&str], references: &mut Vec<&'a str>) {
+    match node {
+        Node::Literal(literal) => references.extend(literal.id.as_deref()),
+        Node::Alias(alias) => {
+            if !aliases.contains(&alias.name.as_str()) {
+                references.push(&alias.name);
+            }
+        }
+        Node::Opcode(opcode) => {
+            for arg in opcode.operand_args.iter().flat_map(|v| &v.args) {
+                if let Some((id, _)) = &arg.binding_id {
+                    references.push(id.trim_start_matches('\''));
+                }
+            }
+            for input in &opcode.inputs {
+                node_references(input, aliases, references);
+            }
+        }
+    }
+}
+
+/// Checks if the given node or any of its inputs is an alias of the given name
+fn is_referencing(node: &Node, name: &str) -> bool {
+    match node {
+        Node::Alias(alias) => alias.name == name,
+        Node::Opcode(opcode) => opcode.inputs.iter().any(|v| is_referencing(v, name)),
+        Node::Literal(_) => false,
+    }
+}
+
+/// Collects the import indexes of the leaves of the given namespace item
+fn import_indexes(item: &NamespaceItem, indexes: &mut Vec<isize>) {
+    match item {
+        NamespaceItem::Leaf(leaf) => {
+            if leaf.import_index != -1 && !indexes.contains(&leaf.import_index) {
+                indexes.push(leaf.import_index);
+            }
+        }
+        NamespaceItem::Node(node) => {
+            for item in node.values() {
+                import_indexes(item, indexes);
+            }
+        }
+    }
+}
+
+/// First segment of a namespace path which is the name of a root namespace item
+fn root_name(path: &str) -> &str {
+    path.trim_start_matches('.')
+        .split('.')
+        .next()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, RwLock};
+    use rain_metadata::Store;
+
+    fn lint_problems(rain_document: &RainDocument) -> Vec<(ErrorCode, String, Offsets, Severity)> {
+        rain_document
+            .problems
+            .iter()
+            .filter(|v| Lint::from_code(v.code).is_some())
+            .map(|v| (v.code, v.msg.clone(), v.position, v.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_lints() {
+        let mut store = Store::new();
+        let (a_hash, _) = store
+            .set_dotrain("---\n#val 1", "file:///a.rain", false)
+            .unwrap();
+        let (b_hash, _) = store
+            .set_dotrain("---\n#other 2", "file:///b.rain", false)
+            .unwrap();
+        let meta_store = Arc::new(RwLock::new(store));
+        let text = format!(
+            r"---
+@a {}
+@b {}
+/* @entrypoint */
+#main
+x: a.val,
+_ _: x used-lit;
+#unused-lit 1
+#used-lit 2
+#dead-lit 3
+#helper
+y: dead-lit,
+helper: 3;",
+            alloy_primitives::hex::encode_prefixed(a_hash),
+            alloy_primitives::hex::encode_prefixed(b_hash),
+        );
+        let pos = |v: &str| {
+            let start = text.find(v).unwrap();
+            [start, start + v.len()]
+        };
+        let import_b = [text.find("@b").unwrap(), text.find("\n/*").unwrap()];
+        let mut rain_document =
+            RainDocument::create(text.clone(), Some(meta_store.clone()), None, None);
+        assert!(rain_document
+            .problems
+            .iter()
+            .all(|v| v.severity == Severity::Warning));
+        assert_eq!(
+            lint_problems(&rain_document),
+            vec![
+                (
+                    ErrorCode::UnusedBinding,
+                    "unused binding: unused-lit".to_owned(),
+                    pos("unused-lit"),
+                    Severity::Warning
+                ),
+                (
+                    ErrorCode::UnusedBinding,
+                    "unused binding: dead-lit".to_owned(),
+                    pos("dead-lit"),
+                    Severity::Warning
+                ),
+                (
+                    ErrorCode::UnusedBinding,
+                    "unused binding: helper".to_owned(),
+                    pos("helper"),
+                    Severity::Warning
+                ),
+                (
+                    ErrorCode::UnusedImport,
+                    "unused import: b".to_owned(),
+                    import_b,
+                    Severity::Warning
+                ),
+                (
+                    ErrorCode::ShadowedName,
+                    "alias helper shadows the binding or namespace of the same name".to_owned(),
+                    [pos("helper:")[0], pos("helper:")[1] - 1],
+                    Severity::Warning
+                ),
+            ]
+        );
+        // warnings do not fail the compose
+        assert!(rain_document.compose(&["main"], None).is_ok());
+
+        // reachability from supplied entrypoints
+        let names = |v: Vec<&Binding>| v.iter().map(|v| v.name.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            names(rain_document.unused_bindings(&["helper"])),
+            vec!["main", "unused-lit", "used-lit"]
+        );
+        assert_eq!(
+            names(rain_document.unused_bindings(&["main", "helper"])),
+            vec!["unused-lit"]
+        );
+        assert_eq!(rain_document.unused_imports(&["helper"]).len(), 2);
+        assert!(rain_document
+            .unused_imports(&["main", "b.other"])
+            .is_empty());
+
+        // denied lints fail the compose
+        rain_document.set_lint_config(LintConfig(HashMap::from([
+            (Lint::UnusedImport, LintLevel::Allow),
+            (Lint::UnusedBinding, LintLevel::Deny),
+            (Lint::ShadowedName, LintLevel::Allow),
+        ])));
+        assert_eq!(
+            lint_problems(&rain_document)
+                .iter()
+                .map(|v| (v.0, v.3))
+                .collect::<Vec<_>>(),
+            vec![
+                (ErrorCode::UnusedBinding, Severity::Error),
+                (ErrorCode::UnusedBinding, Severity::Error),
+                (ErrorCode::UnusedBinding, Severity::Error),
+            ]
+        );
+        assert!(rain_document.compose(&["main"], None).is_err());
+
+        // binding annotations take precedence over document annotations and the config
+        let text = text
+            .replace(
+                "/* @entrypoint */",
+                "/* @allow unused-binding */\n/* @entrypoint */",
+            )
+            .replace(
+                "#helper",
+                "/* @deny unused-binding @warn unused-alias */\n#helper",
+            );
+        let mut rain_document = RainDocument::create(text.clone(), Some(meta_store), None, None);
+        rain_document.set_lint_config(LintConfig(HashMap::from([(
+            Lint::UnusedBinding,
+            LintLevel::Warn,
+        )])));
+        assert_eq!(
+            lint_problems(&rain_document)
+                .iter()
+                .map(|v| (v.1.as_str(), v.3))
+                .collect::<Vec<_>>(),
+            vec![
+                ("unused binding: helper", Severity::Error),
+                ("unused import: b", Severity::Warning),
+                ("unused alias: y", Severity::Warning),
+                ("unused alias: helper", Severity::Warning),
+                (
+                    "alias helper shadows the binding or namespace of the same name",
+                    Severity::Warning
+                ),
+            ]
+        );
+
+        // unused bindings and imports are not checked without declared entrypoints
+        let rain_document =
+            RainDocument::create("---\n#a 1\n#b\n_: a;".to_owned(), None, None, None);
+        assert!(lint_problems(&rain_document).is_empty());
+
+        // quotes in operand args and quote bindings are followed as dependencies
+        let rain_document = RainDocument::create(
+            "---\n#main _: call<'q>();\n#q 'exp\n#exp _: 1;\n#dead 1".to_owned(),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            names(rain_document.unused_bindings(&["main"])),
+            vec!["dead"]
+        );
+    }
+}
@@ -0,0 +1,699 @@
+//! Builds the lossless concrete syntax tree of RainDocument and rainlang texts from their tokens
+
+use super::{
+    blank, tokenize, Token, TokenKind,
+    raindocument::RainDocument,
+    rainlangdocument::RainlangDocument,
+    super::types::{
+        ast::{Offsets, ParsedItem},
+        cst::*,
+        literal::string_literal_end,
+        patterns::{FRONTMATTER_SEPARATOR, NUMERIC_PATTERN, PRAGMA_KEYWORD},
+    },
+};
+
+impl RainDocument {
+    /// Builds the lossless concrete syntax tree of this instance's text
+    pub fn cst(&self) -> SyntaxNode {
+        dotrain_cst(&self.text)
+    }
+}
+
+impl RainlangDocument {
+    /// Builds the lossless concrete syntax tree of this instance's text
+    pub fn cst(&self) -> SyntaxNode {
+        rainlang_cst(&self.text)
+    }
+}
+
+/// Sections of a RainDocument text read from its concrete syntax tree
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DotrainSections {
+    /// comments of the body
+    pub comments: Vec<ParsedItem>,
+    /// import statements (text after `@`) with their comments blanked
+    pub imports: Vec<ParsedItem>,
+    /// bindings (text after `#`) with their comments and imports blanked
+    pub bindings: Vec<ParsedItem>,
+    /// positions of the non whitespace runs that dont belong to any section
+    pub unexpected: Vec<Offsets>,
+}
+
+/// Builds the lossless concrete syntax tree of a RainDocument text
+pub fn dotrain_cst(text: &str) -> SyntaxNode {
+    let mut children = vec![];
+    let body_start = if let Some(splitter) = text.find(FRONTMATTER_SEPARATOR) {
+        let end = splitter + FRONTMATTER_SEPARATOR.len();
+        let mut front_matter = vec![];
+        if splitter > 0 {
+            front_matter.push(token(SyntaxKind::FrontMatterText, text, [0, splitter]));
+        }
+        front_matter.push(token(SyntaxKind::Separator, text, [splitter, end]));
+        children.extend(wrap(SyntaxKind::FrontMatter, front_matter));
+        end
+    } else {
+        0
+    };
+    children.extend(dotrain_body(text, body_start));
+    SyntaxNode {
+        kind: SyntaxKind::Document,
+        position: [0, text.len()],
+        children,
+    }
+}
+
+/// Builds the lossless concrete syntax tree of a rainlang text
+pub fn rainlang_cst(text: &str) -> SyntaxNode {
+    SyntaxNode {
+        kind: SyntaxKind::Rainlang,
+        position: [0, text.len()],
+        children: rainlang(text, &tokenize(text, 0)),
+    }
+}
+
+/// Reads the sections of a RainDocument text from the concrete syntax tree of its body, i.e. the
+/// text after the front matter which starts at the given offset, so the AST is built from the same
+/// import statements and bindings as the CST, a binding's text also spans over the import statements
+/// following it up to the next binding with them blanked, so they can be reported as non top level
+pub fn split_dotrain(text: &str, body_start: usize) -> DotrainSections {
+    let body = SyntaxNode {
+        kind: SyntaxKind::Document,
+        position: [body_start, text.len()],
+        children: dotrain_body(text, body_start),
+    };
+    let mut sections = DotrainSections::default();
+    let comments: Vec<Offsets> = body
+        .tokens()
+        .into_iter()
+        .filter(|v| v.kind == SyntaxKind::Comment)
+        .map(|v| v.position)
+        .collect();
+    sections.comments = comments
+        .iter()
+        .map(|v| ParsedItem(text[v[0]..v[1]].to_owned(), *v))
+        .collect();
+
+    // texts of the sections start after their `@` or `#` token
+    let nodes: Vec<&SyntaxNode> = body.child_nodes().collect();
+    for (i, node) in nodes.iter().enumerate() {
+        let start = node.position[0] + 1;
+        let mut next = nodes[i + 1..].iter();
+        if node.kind == SyntaxKind::Import {
+            let end = next.next().map_or(text.len(), |v| v.position[0]);
+            sections.imports.push(ParsedItem(
+                blank(text, [start, end], &comments, None),
+                [start, end],
+            ));
+        } else {
+            let import_start = next
+                .clone()
+                .take_while(|v| v.kind == SyntaxKind::Import)
+                .map(|v| v.position[0])
+                .next();
+            let end = next
+                .find(|v| v.kind == SyntaxKind::Binding)
+                .map_or(text.len(), |v| v.position[0]);
+            sections.bindings.push(ParsedItem(
+                blank(text, [start, end], &comments, import_start),
+                [start, end],
+            ));
+        }
+    }
+
+    // non whitespace runs before the first import or binding
+    let mut run: Option<Offsets> = None;
+    for element in body.children.iter().take_while(|v| v.as_token().is_some()) {
+        if element.kind().is_trivia() {
+            sections.unexpected.extend(run.take());
+        } else if let Some(run) = &mut run {
+            run[1] = element.position()[1];
+        } else {
+            run = Some(element.position());
+        }
+    }
+    sections.unexpected.extend(run);
+    sections
+}
+
+/// Builds the import statements and bindings of a RainDocument text's body which starts at the
+/// given offset, import statements and bindings end at the next import or binding
+fn dotrain_body(text: &str, body_start: usize) -> Vec<SyntaxElement> {
+    let mut children = vec![];
+    let tokens = tokenize(&text[body_start..], body_start);
+    let mut i = 0;
+    while i < tokens.len() {
+        let kind = tokens[i].kind;
+        if kind == TokenKind::At || kind == TokenKind::Hash {
+            let end = tokens[i + 1..]
+                .iter()
+                .position(|v| v.kind == TokenKind::At || v.kind == TokenKind::Hash)
+                .map_or(tokens.len(), |v| i + 1 + v);
+            if kind == TokenKind::At {
+                children.extend(import(text, &tokens[i..end]));
+            } else {
+                children.extend(binding(text, &tokens[i..end]));
+            }
+            i = end;
+        } else {
+            children.push(lexer_token(text, &tokens[i], SyntaxKind::Word));
+            i += 1;
+        }
+    }
+    children
+}
+
+/// Builds an import statement node from its tokens, starting with `@`
+fn import(text: &str, tokens: &[Token]) -> Vec<SyntaxElement> {
+    let mut elements = vec![token(SyntaxKind::At, text, tokens[0].position)];
+    let mut words = 0;
+    let mut has_name = false;
+    let mut config_start = None;
+    for (i, v) in tokens.iter().enumerate().skip(1) {
+        if matches!(v.kind, TokenKind::Whitespace | TokenKind::Comment) {
+            elements.push(lexer_token(text, v, SyntaxKind::Word));
+            continue;
+        }
+        words += 1;
+        let kind = match words {
+            1 if !text[v.position[0]..].starts_with("0x") => {
+                has_name = true;
+                SyntaxKind::Name
+            }
+            1 => SyntaxKind::MetaHash,
+            2 if has_name => SyntaxKind::MetaHash,
+            _ => {
+                config_start = Some(i);
+                break;
+            }
+        };
+        elements.push(lexer_token(text, v, kind));
+    }
+    if let Some(start) = config_start {
+        let config = tokens[start..]
+            .iter()
+            .map(|v| lexer_token(text, v, SyntaxKind::Word))
+            .collect();
+        elements.extend(wrap(SyntaxKind::ImportConfig, config));
+    }
+    wrap(SyntaxKind::Import, elements)
+}
+
+/// Builds a binding node from its tokens, starting with `#`
+fn binding(text: &str, tokens: &[Token]) -> Vec<SyntaxElement> {
+    let mut elements = vec![token(SyntaxKind::HashSign, text, tokens[0].position)];
+    // name spans up to the first whitespace or comment, same as it is parsed into the AST
+    let body_start = tokens[1..]
+        .iter()
+        .position(|v| matches!(v.kind, TokenKind::Whitespace | TokenKind::Comment))
+        .map_or(tokens.len(), |v| v + 1);
+    if body_start > 1 {
+        elements.push(token(
+            SyntaxKind::Name,
+            text,
+            [tokens[1].position[0], tokens[body_start - 1].position[1]],
+        ));
+    }
+    let body = &tokens[body_start..];
+    if let (Some(first), Some(last)) = (body.first(), body.last()) {
+        let comments: Vec<Offsets> = body
+            .iter()
+            .filter(|v| v.kind == TokenKind::Comment)
+            .map(|v| v.position)
+            .collect();
+        let content = blank(text, [first.position[0], last.position[1]], &comments, None);
+        let content = content.trim();
+        let (kind, word_kind) = if content.is_empty() {
+            (None, SyntaxKind::Word)
+        } else if RainDocument::is_elided(content).is_some() {
+            (Some(SyntaxKind::ElidedBody), SyntaxKind::Word)
+        } else if RainDocument::is_literal(content).is_some() {
+            (Some(SyntaxKind::LiteralBody), SyntaxKind::Literal)
+        } else if RainDocument::is_quote(content, 0).is_some() {
+            (Some(SyntaxKind::QuoteBody), SyntaxKind::Word)
+        } else {
+            (None, SyntaxKind::Word)
+        };
+        if let Some(kind) = kind {
+            let body = body
+                .iter()
+                .map(|v| lexer_token(text, v, word_kind))
+                .collect();
+            elements.extend(wrap(kind, body));
+        } else {
+            elements.extend(wrap(SyntaxKind::Rainlang, rainlang(text, body)));
+        }
+    }
+    wrap(SyntaxKind::Binding, elements)
+}
+
+/// Builds the pragma and sources of a rainlang text from its tokens
+fn rainlang(text: &str, tokens: &[Token]) -> Vec<SyntaxElement> {
+    let tokens = rainlang_tokens(text, tokens);
+    let mut elements = vec![];
+    let mut i = 0;
+
+    // pragma is the keyword followed by its literals at the start of the text
+    let first = tokens.iter().position(|v| !v.kind.is_trivia());
+    if let Some(first) = first.filter(|v| tokens[*v].text == PRAGMA_KEYWORD) {
+        let mut end = first + 1;
+        while tokens
+            .get(end)
+            .is_some_and(|v| v.kind.is_trivia() || v.kind == SyntaxKind::Literal)
+        {
+            end += 1;
+        }
+        elements.extend(wrap(SyntaxKind::Pragma, into_elements(&tokens[..end])));
+        i = end;
+    }
+
+    while i < tokens.len() {
+        let end = tokens[i..]
+            .iter()
+            .position(|v| v.kind == SyntaxKind::Semi)
+            .map_or(tokens.len(), |v| i + v + 1);
+        elements.extend(wrap(
+            SyntaxKind::Source,
+            delimited(&tokens[i..end], SyntaxKind::Semi, |source| {
+                let mut lines = vec![];
+                let mut j = 0;
+                while j < source.len() {
+                    let end = source[j..]
+                        .iter()
+                        .position(|v| v.kind == SyntaxKind::Comma)
+                        .map_or(source.len(), |v| j + v + 1);
+                    lines.extend(wrap(
+                        SyntaxKind::Line,
+                        delimited(&source[j..end], SyntaxKind::Comma, line),
+                    ));
+                    j = end;
+                }
+                lines
+            }),
+        ));
+        i = end;
+    }
+    elements
+}
+
+/// Builds the LHS and RHS of a rainlang line from its tokens
+fn line(tokens: &[SyntaxToken]) -> Vec<SyntaxElement> {
+    if let Some(colon) = tokens.iter().position(|v| v.kind == SyntaxKind::Colon) {
+        let mut elements = wrap(SyntaxKind::Lhs, into_elements(&tokens[..colon]));
+        elements.push(SyntaxElement::Token(tokens[colon].clone()));
+        elements.extend(wrap(
+            SyntaxKind::Rhs,
+            rhs(&tokens[colon + 1..], &mut 0, false),
+        ));
+        elements
+    } else {
+        into_elements(tokens)
+    }
+}
+
+/// Builds the opcodes of a RHS (or an opcode's inputs if nested) from its tokens
+fn rhs(tokens: &[SyntaxToken], i: &mut usize, nested: bool) -> Vec<SyntaxElement> {
+    let mut elements = vec![];
+    while *i < tokens.len() {
+        let current = &tokens[*i];
+        if nested && current.kind == SyntaxKind::RParen {
+            break;
+        }
+        *i += 1;
+        let is_adjacent =
+            |v: &SyntaxToken, kind: SyntaxKind, end: usize| v.kind == kind && v.position[0] == end;
+        let next = tokens.get(*i);
+        if current.kind != SyntaxKind::Word
+            || !next.is_some_and(|v| {
+                is_adjacent(v, SyntaxKind::LAngle, current.position[1])
+                    || is_adjacent(v, SyntaxKind::LParen, current.position[1])
+            })
+        {
+            elements.push(SyntaxElement::Token(current.clone()));
+            continue;
+        }
+
+        let mut opcode = vec![SyntaxElement::Token(current.clone())];
+        if tokens[*i].kind == SyntaxKind::LAngle {
+            let end = tokens[*i..]
+                .iter()
+                .position(|v| v.kind == SyntaxKind::RAngle)
+                .map_or(tokens.len(), |v| *i + v + 1);
+            opcode.extend(wrap(
+                SyntaxKind::OperandArgs,
+                into_elements(&tokens[*i..end]),
+            ));
+            *i = end;
+        }
+        let end = opcode.last().unwrap().position()[1];
+        if tokens
+            .get(*i)
+            .is_some_and(|v| is_adjacent(v, SyntaxKind::LParen, end))
+        {
+            opcode.push(SyntaxElement::Token(tokens[*i].clone()));
+            *i += 1;
+            opcode.extend(rhs(tokens, i, true));
+            if let Some(rparen) = tokens.get(*i) {
+                opcode.push(SyntaxElement::Token(rparen.clone()));
+                *i += 1;
+            }
+        }
+        elements.extend(wrap(SyntaxKind::Opcode, opcode));
+    }
+    elements
+}
+
+/// Builds the elements of the given tokens with the given callback excluding their
+/// ending delimiter which is then included as the last element
+fn delimited(
+    tokens: &[SyntaxToken],
+    delimiter: SyntaxKind,
+    build: impl Fn(&[SyntaxToken]) -> Vec<SyntaxElement>,
+) -> Vec<SyntaxElement> {
+    if let Some((last, rest)) = tokens.split_last().filter(|v| v.0.kind == delimiter) {
+        let mut elements = build(rest);
+        elements.push(SyntaxElement::Token(last.clone()));
+        elements
+    } else {
+        build(tokens)
+    }
+}
+
+/// Splits the words of the given tokens into rainlang tokens, ie punctuations and literals,
+/// a string or sub parser literal spans until its end even if it contains whitespaces
+fn rainlang_tokens(text: &str, tokens: &[Token]) -> Vec<SyntaxToken> {
+    let mut result: Vec<SyntaxToken> = vec![];
+    for v in tokens {
+        if v.kind != TokenKind::Word {
+            if let SyntaxElement::Token(token) = lexer_token(text, v, SyntaxKind::Word) {
+                result.push(token);
+            }
+            continue;
+        }
+        let word = &text[v.position[0]..v.position[1]];
+        let mut start = 0;
+        while start < word.len() {
+            let rest = &word[start..];
+            let len = match rest.as_bytes()[0] {
+                b':' | b'(' | b')' | b'<' | b'>' => 1,
+                b'"' => string_literal_end(rest).unwrap_or(rest.len()),
+                b'[' => rest.find(']').map_or(rest.len(), |v| v + 1),
+                _ => rest.find([':', '(', ')', '<', '>']).unwrap_or(rest.len()),
+            };
+            let piece = &rest[..len];
+            let kind = match piece {
+                ":" => SyntaxKind::Colon,
+                "(" => SyntaxKind::LParen,
+                ")" => SyntaxKind::RParen,
+                "<" => SyntaxKind::LAngle,
+                ">" => SyntaxKind::RAngle,
+                _ if piece.starts_with(['"', '[']) || NUMERIC_PATTERN.is_match(piece) => {
+                    SyntaxKind::Literal
+                }
+                _ => SyntaxKind::Word,
+            };
+            let position = [v.position[0] + start, v.position[0] + start + len];
+            result.push(SyntaxToken {
+                kind,
+                text: piece.to_owned(),
+                position,
+            });
+            start += len;
+        }
+    }
+
+    // merge the unended string and sub parser literals with their following tokens up to their
+    // end, or up to their last non whitespace token if they are not ended
+    let mut merged: Vec<SyntaxToken> = vec![];
+    let mut i = 0;
+    while i < result.len() {
+        let mut token = result[i].clone();
+        i += 1;
+        let end = if token.text.starts_with('"') {
+            Some('"')
+        } else if token.text.starts_with('[') {
+            Some(']')
+        } else {
+            None
+        };
+        // escaped quotes do not end a string literal
+        let is_ended = |v: &str, end: char| {
+            if end == '"' {
+                string_literal_end(v).is_some()
+            } else {
+                v.len() > 1 && v.ends_with(end)
+            }
+        };
+        if let Some(end) = end.filter(|v| !is_ended(&token.text, *v)) {
+            let mut last = None;
+            for (j, next) in result[i..].iter().enumerate() {
+                if next.kind == SyntaxKind::Whitespace {
+                    continue;
+                }
+                if !matches!(next.kind, SyntaxKind::Word | SyntaxKind::Literal) {
+                    break;
+                }
+                last = Some(i + j);
+                if is_ended(&text[token.position[0]..next.position[1]], end) {
+                    break;
+                }
+            }
+            if let Some(last) = last {
+                for next in &result[i..=last] {
+                    token.text.push_str(&next.text);
+                    token.position[1] = next.position[1];
+                }
+                i = last + 1;
+            }
+        }
+        merged.push(token);
+    }
+    merged
+}
+
+/// Wraps the given elements in a node of the given kind, leading and trailing trivia is left out
+fn wrap(kind: SyntaxKind, mut elements: Vec<SyntaxElement>) -> Vec<SyntaxElement> {
+    let Some(start) = elements.iter().position(|v| !v.kind().is_trivia()) else {
+        return elements;
+    };
+    let end = elements
+        .iter()
+        .rposition(|v| !v.kind().is_trivia())
+        .unwrap()
+        + 1;
+    let trailing = elements.split_off(end);
+    let children = elements.split_off(start);
+    elements.push(SyntaxElement::Node(SyntaxNode {
+        kind,
+        position: [
+            children[0].position()[0],
+            children[end - start - 1].position()[1],
+        ],
+        children,
+    }));
+    elements.extend(trailing);
+    elements
+}
+
+fn into_elements(tokens: &[SyntaxToken]) -> Vec<SyntaxElement> {
+    tokens.iter().cloned().map(SyntaxElement::Token).collect()
+}
+
+fn token(kind: SyntaxKind, text: &str, position: Offsets) -> SyntaxElement {
+    SyntaxElement::Token(SyntaxToken {
+        kind,
+        text: text[position[0]..position[1]].to_owned(),
+        position,
+    })
+}
+
+/// Converts a lexer token to a CST token, words get the given kind
+fn lexer_token(text: &str, v: &Token, word_kind: SyntaxKind) -> SyntaxElement {
+    let kind = match v.kind {
+        TokenKind::Whitespace => SyntaxKind::Whitespace,
+        TokenKind::Comment => SyntaxKind::Comment,
+        TokenKind::At => SyntaxKind::At,
+        TokenKind::Hash => SyntaxKind::HashSign,
+        TokenKind::Semi => SyntaxKind::Semi,
+        TokenKind::Comma => SyntaxKind::Comma,
+        TokenKind::Word => word_kind,
+    };
+    token(kind, text, v.position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// checks that every byte of the text belongs to a token and nodes are
+    /// spanning from their first to their last token
+    fn assert_lossless(node: &SyntaxNode, text: &str) {
+        let mut cursor = node.position[0];
+        for child in &node.children {
+            assert_eq!(child.position()[0], cursor);
+            cursor = child.position()[1];
+            match child {
+                SyntaxElement::Node(v) => {
+                    assert!(!v.children.first().unwrap().kind().is_trivia());
+                    assert!(!v.children.last().unwrap().kind().is_trivia());
+                    assert_lossless(v, text);
+                }
+                SyntaxElement::Token(v) => {
+                    assert_eq!(v.text, text[v.position[0]..v.position[1]]);
+                }
+            }
+        }
+        assert_eq!(cursor, node.position[1]);
+    }
+
+    #[test]
+    fn test_dotrain_cst() {
+        let text = r#"some front matter
+---
+@ns 0x1234 'a 'b
+/* main entrypoint */
+#main
+_: add(1 2),
+_ _: mul<1 2>(x "a b") /* inline */;
+
+#x 4e18
+#elided ! not here
+"#;
+        let cst = dotrain_cst(text);
+        assert_lossless(&cst, text);
+        assert_eq!(cst.to_string(), text);
+
+        let kinds = cst
+            .child_nodes()
+            .map(|v| v.kind)
+            .collect::<Vec<SyntaxKind>>();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::FrontMatter,
+                SyntaxKind::Import,
+                SyntaxKind::Binding,
+                SyntaxKind::Binding,
+                SyntaxKind::Binding,
+            ]
+        );
+
+        let import = cst.child_node(SyntaxKind::Import).unwrap();
+        assert_eq!(import.child_token(SyntaxKind::Name).unwrap().text, "ns");
+        assert_eq!(
+            import.child_token(SyntaxKind::MetaHash).unwrap().text,
+            "0x1234"
+        );
+        assert_eq!(
+            import
+                .child_node(SyntaxKind::ImportConfig)
+                .unwrap()
+                .to_string(),
+            "'a 'b"
+        );
+
+        // comment before a binding is a trivia of the document
+        assert!(cst
+            .child_tokens()
+            .any(|v| v.kind == SyntaxKind::Comment && v.text == "/* main entrypoint */"));
+
+        let main = cst.node_at(text.find("main\n").unwrap(), SyntaxKind::Binding);
+        let main = main.unwrap();
+        assert_eq!(main.child_token(SyntaxKind::Name).unwrap().text, "main");
+        let sources = main.descendants(SyntaxKind::Source);
+        assert_eq!(sources.len(), 1);
+        let lines = sources[0]
+            .child_nodes()
+            .filter(|v| v.kind == SyntaxKind::Line)
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["_: add(1 2),", "_ _: mul<1 2>(x \"a b\")"]);
+        let opcodes = main.descendants(SyntaxKind::Opcode);
+        assert_eq!(opcodes.len(), 2);
+        assert_eq!(
+            opcodes[1]
+                .child_node(SyntaxKind::OperandArgs)
+                .unwrap()
+                .to_string(),
+            "<1 2>"
+        );
+
+        let literal = cst.node_at(text.find("4e18").unwrap(), SyntaxKind::LiteralBody);
+        assert_eq!(literal.unwrap().to_string(), "4e18");
+        assert!(cst
+            .node_at(text.find("! not").unwrap(), SyntaxKind::ElidedBody)
+            .is_some());
+
+        // AST sections are read from the CST nodes, a binding's section spans over the
+        // import statements following it with them blanked
+        let text = "---\n#a 1\n@imp 0x12\n#b 2";
+        let cst = dotrain_cst(text);
+        let sections = split_dotrain(text, 4);
+        assert_eq!(
+            sections.bindings,
+            vec![
+                ParsedItem("a 1\n         \n".to_owned(), [5, 19]),
+                ParsedItem("b 2".to_owned(), [20, 23]),
+            ]
+        );
+        assert_eq!(
+            sections.imports,
+            vec![ParsedItem("imp 0x12\n".to_owned(), [10, 19])]
+        );
+        let starts = cst
+            .child_nodes()
+            .skip(1)
+            .map(|v| v.position[0] + 1)
+            .collect::<Vec<_>>();
+        assert_eq!(starts, vec![5, 10, 20]);
+
+        // binding names end at any whitespace in both trees
+        let text = "---\n#a\u{a0}1\n#b\t2";
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        let names = dotrain_cst(text)
+            .descendants(SyntaxKind::Binding)
+            .iter()
+            .map(|v| v.child_token(SyntaxKind::Name).unwrap().text.clone())
+            .collect::<Vec<_>>();
+        let ast_names = rain_document
+            .bindings()
+            .iter()
+            .map(|v| v.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(names, ast_names);
+        assert_eq!(rain_document.bindings()[0].content, "1");
+    }
+
+    #[test]
+    fn test_rainlang_cst() {
+        let text = "using-words-from 0x1234 \"abc\"\n_: opcode<1>(\"a b\" 2),\n_: x;";
+        let cst = rainlang_cst(text);
+        assert_lossless(&cst, text);
+        assert_eq!(
+            cst.child_node(SyntaxKind::Pragma).unwrap().to_string(),
+            "using-words-from 0x1234 \"abc\""
+        );
+        let literals = cst
+            .tokens()
+            .into_iter()
+            .filter(|v| v.kind == SyntaxKind::Literal)
+            .map(|v| v.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(literals, vec!["0x1234", "\"abc\"", "1", "\"a b\"", "2"]);
+        let lhs = cst.descendants(SyntaxKind::Lhs);
+        assert_eq!(lhs.len(), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn test_cst_is_lossless(text in "[ab0x1/*@#;,:()<>\"\\[\\]!'. \n-∂]{0,80}") {
+            let cst = dotrain_cst(&text);
+            assert_lossless(&cst, &text);
+            prop_assert_eq!(cst.to_string(), text.clone());
+            let cst = rainlang_cst(&text);
+            assert_lossless(&cst, &text);
+            prop_assert_eq!(cst.to_string(), text);
+        }
+    }
+}
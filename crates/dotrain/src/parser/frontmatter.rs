@@ -0,0 +1,1309 @@
+//! Structured front matter of RainDocuments
+//!
+//! A front matter is parsed as JSON if it starts with `{` or `[`, otherwise as YAML, the supported
+//! YAML is the subset that is commonly used for configurations: block and flow mappings and sequences,
+//! plain, single and double quoted and `|`/`>` block scalars and comments, anchors, aliases, tags and
+//! directives are not supported and are reported as errors. Plain scalars resolve to null, booleans
+//! and decimal numbers (integers that do not fit in 64 bits are kept as strings) and to strings otherwise.
+
+use std::collections::HashMap;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Number, Value};
+use super::{
+    raindocument::RainDocument,
+    super::{
+        error::ErrorCode,
+        types::{ast::*, patterns::*},
+    },
+};
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Type of a JSON value type keyword of a front matter schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum FrontMatterType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+/// Type of the `type` keyword of a front matter schema, either a single type or a list of types
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum FrontMatterTypes {
+    Single(FrontMatterType),
+    Multiple(Vec<FrontMatterType>),
+}
+
+/// Schema of a RainDocument front matter, a subset of JSON Schema that supports `type`, `properties`,
+/// `required`, `additionalProperties` (as a boolean), `items`, `enum`, `pattern`, `minimum` and
+/// `maximum` keywords, any other keyword is ignored
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct FrontMatterSchema {
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub types: Option<FrontMatterTypes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub properties: Option<HashMap<String, FrontMatterSchema>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub required: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub additional_properties: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub items: Option<Box<FrontMatterSchema>>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional, type = "any[]"))]
+    pub enum_values: Option<Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional, type = "string"))]
+    pub pattern: Option<FrontMatterPattern>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub maximum: Option<f64>,
+}
+
+/// Type of the `pattern` keyword of a front matter schema, the regex is compiled once when the
+/// pattern is created or deserialized so an invalid pattern is an error rather than being ignored
+#[derive(Debug, Clone)]
+pub struct FrontMatterPattern(Regex);
+
+impl FrontMatterPattern {
+    /// Compiles the given pattern, errors if it is not a valid regex
+    pub fn new(pattern: &str) -> Result<FrontMatterPattern, regex::Error> {
+        Ok(FrontMatterPattern(Regex::new(pattern)?))
+    }
+
+    /// The pattern text
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Determines if the given value matches this pattern
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl PartialEq for FrontMatterPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl TryFrom<String> for FrontMatterPattern {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        FrontMatterPattern::new(&value)
+            .map_err(|e| format!("invalid front matter schema pattern {}: {}", value, e))
+    }
+}
+
+impl Serialize for FrontMatterPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FrontMatterPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FrontMatterPattern::try_from(String::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Type of a parsed front matter value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FrontMatterItem {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<FrontMatterNode>),
+    Object(Vec<(ParsedItem, FrontMatterNode)>),
+}
+
+/// Type of a parsed front matter value with its position in the text, object keys
+/// are kept with their positions in the order they appear in the text
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FrontMatterNode {
+    pub(crate) item: FrontMatterItem,
+    pub(crate) position: Offsets,
+}
+
+impl FrontMatterNode {
+    fn new(item: FrontMatterItem, position: Offsets) -> FrontMatterNode {
+        FrontMatterNode { item, position }
+    }
+
+    /// Converts this node to a JSON value
+    pub(crate) fn to_value(&self) -> Value {
+        match &self.item {
+            FrontMatterItem::Null => Value::Null,
+            FrontMatterItem::Bool(v) => Value::Bool(*v),
+            FrontMatterItem::Number(v) => Value::Number(v.clone()),
+            FrontMatterItem::String(v) => Value::String(v.clone()),
+            FrontMatterItem::Array(v) => Value::Array(v.iter().map(|v| v.to_value()).collect()),
+            FrontMatterItem::Object(v) => Value::Object(
+                v.iter()
+                    .map(|(key, value)| (key.0.clone(), value.to_value()))
+                    .collect::<Map<_, _>>(),
+            ),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match &self.item {
+            FrontMatterItem::Null => "null",
+            FrontMatterItem::Bool(_) => "boolean",
+            FrontMatterItem::Number(_) => "number",
+            FrontMatterItem::String(_) => "string",
+            FrontMatterItem::Array(_) => "array",
+            FrontMatterItem::Object(_) => "object",
+        }
+    }
+}
+
+impl RainDocument {
+    /// This instance's front matter parsed as YAML (or as JSON if it starts with `{` or `[`),
+    /// None if the front matter is empty or is not valid
+    pub fn front_matter_value(&self) -> Option<Value> {
+        parse_front_matter(self.front_matter())
+            .ok()
+            .flatten()
+            .map(|v| v.to_value())
+    }
+
+    /// This instance's front matter schema
+    pub fn front_matter_schema(&self) -> &Option<FrontMatterSchema> {
+        &self.front_matter_schema
+    }
+
+    /// Sets the schema that the front matter is validated against and revalidates the front
+    /// matter right away without reparsing, front matter syntax errors and schema violations
+    /// are reported as top problems only when a schema is set
+    pub fn set_front_matter_schema(&mut self, schema: Option<FrontMatterSchema>) {
+        self.front_matter_schema = schema;
+        self.problems.retain(|v| {
+            !matches!(
+                v.code,
+                ErrorCode::InvalidFrontMatter | ErrorCode::InvalidFrontMatterValue
+            )
+        });
+        self.validate_front_matter();
+    }
+
+    /// Validates the front matter against this instance's schema if there is one and adds
+    /// the found problems to the top problems, imported dotrains are not validated
+    pub(crate) fn validate_front_matter(&mut self) {
+        if self.import_depth == 0 && NON_EMPTY_PATTERN.is_match(&self.text) {
+            if let Some(schema) = &self.front_matter_schema {
+                self.problems
+                    .extend(schema.validate_front_matter(self.front_matter()));
+            }
+        }
+    }
+}
+
+impl FrontMatterType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FrontMatterType::Null => "null",
+            FrontMatterType::Boolean => "boolean",
+            FrontMatterType::Integer => "integer",
+            FrontMatterType::Number => "number",
+            FrontMatterType::String => "string",
+            FrontMatterType::Array => "array",
+            FrontMatterType::Object => "object",
+        }
+    }
+
+    fn matches(&self, item: &FrontMatterItem) -> bool {
+        match (self, item) {
+            (FrontMatterType::Null, FrontMatterItem::Null)
+            | (FrontMatterType::Boolean, FrontMatterItem::Bool(_))
+            | (FrontMatterType::Number, FrontMatterItem::Number(_))
+            | (FrontMatterType::String, FrontMatterItem::String(_))
+            | (FrontMatterType::Array, FrontMatterItem::Array(_))
+            | (FrontMatterType::Object, FrontMatterItem::Object(_)) => true,
+            (FrontMatterType::Integer, FrontMatterItem::Number(v)) => {
+                v.is_i64() || v.is_u64() || v.as_f64().is_some_and(|v| v.fract() == 0.0)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FrontMatterSchema {
+    /// Validates the given front matter text against this schema, the syntax error or the schema
+    /// violations are returned as problems positioned at the offending items, an empty front
+    /// matter is validated as null
+    pub fn validate_front_matter(&self, front_matter: &str) -> Vec<Problem> {
+        match parse_front_matter(front_matter) {
+            Ok(node) => {
+                let node =
+                    node.unwrap_or_else(|| FrontMatterNode::new(FrontMatterItem::Null, [0, 0]));
+                let mut problems = vec![];
+                self.validate(&node, "", &mut problems);
+                problems
+            }
+            Err(problem) => vec![problem],
+        }
+    }
+
+    /// Validates a node at the given path (JSON pointer) against this schema
+    fn validate(&self, node: &FrontMatterNode, path: &str, problems: &mut Vec<Problem>) {
+        let violation = |msg: &str, position: Offsets| {
+            let path = if path.is_empty() { "/" } else { path };
+            ErrorCode::InvalidFrontMatterValue.to_problem(vec![path, msg], position)
+        };
+
+        let types = match &self.types {
+            Some(FrontMatterTypes::Single(v)) => vec![*v],
+            Some(FrontMatterTypes::Multiple(v)) => v.clone(),
+            None => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|v| v.matches(&node.item)) {
+            let expected = types.iter().map(|v| v.as_str()).collect::<Vec<_>>();
+            problems.push(violation(
+                &format!(
+                    "expected {}, found {}",
+                    expected.join(" or "),
+                    node.type_name()
+                ),
+                node.position,
+            ));
+            return;
+        }
+
+        if let Some(values) = &self.enum_values {
+            if !values.contains(&node.to_value()) {
+                problems.push(violation(
+                    &format!(
+                        "expected one of {}",
+                        serde_json::to_string(values).unwrap_or_default()
+                    ),
+                    node.position,
+                ));
+            }
+        }
+
+        match &node.item {
+            FrontMatterItem::String(value) => {
+                if let Some(pattern) = &self.pattern {
+                    if !pattern.is_match(value) {
+                        problems.push(violation(
+                            &format!("expected a string matching {}", pattern.as_str()),
+                            node.position,
+                        ));
+                    }
+                }
+            }
+            FrontMatterItem::Number(value) => {
+                let value = value.as_f64().unwrap_or_default();
+                if let Some(minimum) = self.minimum.filter(|v| value < *v) {
+                    problems.push(violation(
+                        &format!("expected a number >= {}", minimum),
+                        node.position,
+                    ));
+                }
+                if let Some(maximum) = self.maximum.filter(|v| value > *v) {
+                    problems.push(violation(
+                        &format!("expected a number <= {}", maximum),
+                        node.position,
+                    ));
+                }
+            }
+            FrontMatterItem::Array(items) => {
+                if let Some(schema) = &self.items {
+                    for (i, item) in items.iter().enumerate() {
+                        schema.validate(item, &format!("{}/{}", path, i), problems);
+                    }
+                }
+            }
+            FrontMatterItem::Object(entries) => {
+                for key in self.required.iter().flatten() {
+                    if !entries.iter().any(|(k, _)| &k.0 == key) {
+                        problems.push(violation(
+                            &format!("missing required property {}", key),
+                            node.position,
+                        ));
+                    }
+                }
+                for (key, value) in entries {
+                    if let Some(schema) = self.properties.as_ref().and_then(|v| v.get(&key.0)) {
+                        schema.validate(value, &format!("{}/{}", path, key.0), problems);
+                    } else if self.additional_properties == Some(false) {
+                        problems.push(violation(&format!("unknown property {}", key.0), key.1));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parses the given front matter text, None if it is empty (has only whitespaces and comments)
+pub(crate) fn parse_front_matter(text: &str) -> Result<Option<FrontMatterNode>, Problem> {
+    let start = text.len() - text.trim_start().len();
+    if text[start..].starts_with(['{', '[']) {
+        let mut parser = FrontMatterParser::new(text, true);
+        parser.pos = start;
+        let node = parser.flow_value()?;
+        parser.skip_space();
+        if parser.pos < text.len() {
+            return Err(error(
+                "unexpected characters after value",
+                [parser.pos, text.trim_end().len()],
+            ));
+        }
+        return Ok(Some(node));
+    }
+    let mut parser = FrontMatterParser::new(text, false);
+    if parser.next_line()?.is_none() {
+        return Ok(None);
+    }
+    let node = parser.block_node(None)?;
+    if let Some(line) = parser.next_line()? {
+        return Err(error("unexpected content", [line.start, line.end]));
+    }
+    Ok(Some(node))
+}
+
+fn error(msg: &str, position: Offsets) -> Problem {
+    ErrorCode::InvalidFrontMatter.to_problem(vec![msg], position)
+}
+
+/// Checks if the given line content is empty or a comment
+fn is_blank(content: &str) -> bool {
+    let content = content.trim_start();
+    content.is_empty() || content.starts_with('#')
+}
+
+/// Checks if the given line content is a block sequence entry
+fn is_dash(content: &str) -> bool {
+    content
+        .strip_prefix('-')
+        .is_some_and(|v| v.is_empty() || v.starts_with([' ', '\t']))
+}
+
+/// Resolves a plain scalar
+fn resolve_plain(value: &str) -> FrontMatterItem {
+    match value {
+        "" | "~" | "null" | "Null" | "NULL" => FrontMatterItem::Null,
+        "true" | "True" | "TRUE" => FrontMatterItem::Bool(true),
+        "false" | "False" | "FALSE" => FrontMatterItem::Bool(false),
+        _ if YAML_INT_PATTERN.is_match(value) => value
+            .parse::<i64>()
+            .map(Number::from)
+            .or_else(|_| value.parse::<u64>().map(Number::from))
+            .map_or(
+                FrontMatterItem::String(value.to_owned()),
+                FrontMatterItem::Number,
+            ),
+        _ if YAML_FLOAT_PATTERN.is_match(value) => {
+            value.parse::<f64>().ok().and_then(Number::from_f64).map_or(
+                FrontMatterItem::String(value.to_owned()),
+                FrontMatterItem::Number,
+            )
+        }
+        _ => FrontMatterItem::String(value.to_owned()),
+    }
+}
+
+/// Type of a line of a YAML front matter, `begin` is the start of the line, `start` is the start
+/// of its content after the indentation and `end` is the end of the line excluding the line break
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    begin: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Line {
+    fn indent(&self) -> usize {
+        self.start - self.begin
+    }
+}
+
+/// Recursive descent parser of front matter texts, block YAML is parsed line by line and
+/// flow collections, quoted scalars and JSON are parsed char by char
+struct FrontMatterParser<'a> {
+    text: &'a str,
+    json: bool,
+    pos: usize,
+    lines: Vec<Line>,
+    line: usize,
+}
+
+impl<'a> FrontMatterParser<'a> {
+    fn new(text: &'a str, json: bool) -> FrontMatterParser<'a> {
+        let mut lines = vec![];
+        let mut begin = 0;
+        for line in text.split('\n') {
+            let content = line.strip_suffix('\r').unwrap_or(line);
+            lines.push(Line {
+                begin,
+                start: begin + content.len() - content.trim_start_matches(' ').len(),
+                end: begin + content.len(),
+            });
+            begin += line.len() + 1;
+        }
+        FrontMatterParser {
+            text,
+            json,
+            pos: 0,
+            lines,
+            line: 0,
+        }
+    }
+
+    /// Skips the blank lines and returns the current line
+    fn next_line(&mut self) -> Result<Option<Line>, Problem> {
+        while let Some(line) = self.lines.get(self.line) {
+            if is_blank(&self.text[line.start..line.end]) {
+                self.line += 1;
+            } else if self.text[line.start..].starts_with('\t') {
+                return Err(error(
+                    "tabs are not allowed in indentation",
+                    [line.begin, line.start + 1],
+                ));
+            } else {
+                return Ok(Some(*line));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Parses the block node that starts at the current line, `parent` is the indentation
+    /// of the enclosing block collection
+    fn block_node(&mut self, parent: Option<usize>) -> Result<FrontMatterNode, Problem> {
+        let line = self.lines[self.line];
+        if is_dash(&self.text[line.start..line.end]) {
+            self.block_sequence(line.indent())
+        } else if self.mapping_key(line)?.is_some() {
+            self.block_mapping(line.indent())
+        } else {
+            self.inline_value(parent)
+        }
+    }
+
+    fn block_sequence(&mut self, indent: usize) -> Result<FrontMatterNode, Problem> {
+        let start = self.lines[self.line].start;
+        let mut items = vec![];
+        while let Some(line) = self.next_line()? {
+            if line.indent() < indent || !is_dash(&self.text[line.start..line.end]) {
+                break;
+            }
+            if line.indent() > indent {
+                return Err(error(
+                    "bad indentation of a sequence entry",
+                    [line.start, line.end],
+                ));
+            }
+            let content = &self.text[line.start + 1..line.end];
+            let item_start = line.end - content.trim_start().len();
+            let item = if is_blank(content) {
+                self.line += 1;
+                match self.next_line()? {
+                    Some(next) if next.indent() > indent => self.block_node(Some(indent))?,
+                    _ => FrontMatterNode::new(
+                        FrontMatterItem::Null,
+                        [line.start + 1, line.start + 1],
+                    ),
+                }
+            } else {
+                // the item content is parsed as a line indented at its column
+                self.lines[self.line].start = item_start;
+                self.block_node(Some(indent))?
+            };
+            items.push(item);
+        }
+        let end = items.last().map_or(start + 1, |v| v.position[1]);
+        Ok(FrontMatterNode::new(
+            FrontMatterItem::Array(items),
+            [start, end],
+        ))
+    }
+
+    fn block_mapping(&mut self, indent: usize) -> Result<FrontMatterNode, Problem> {
+        let start = self.lines[self.line].start;
+        let mut entries: Vec<(ParsedItem, FrontMatterNode)> = vec![];
+        while let Some(line) = self.next_line()? {
+            if line.indent() < indent {
+                break;
+            }
+            if line.indent() > indent {
+                return Err(error(
+                    "bad indentation of a mapping entry",
+                    [line.start, line.end],
+                ));
+            }
+            let Some((key, value_start)) = self.mapping_key(line)? else {
+                return Err(error("expected a mapping entry", [line.start, line.end]));
+            };
+            if entries.iter().any(|(k, _)| k.0 == key.0) {
+                return Err(error(&format!("duplicate key: {}", key.0), key.1));
+            }
+            let content = &self.text[value_start..line.end];
+            let value = if is_blank(content) {
+                self.line += 1;
+                match self.next_line()? {
+                    Some(next) if next.indent() > indent => self.block_node(Some(indent))?,
+                    Some(next)
+                        if next.indent() == indent && is_dash(&self.text[next.start..next.end]) =>
+                    {
+                        self.block_sequence(indent)?
+                    }
+                    _ => FrontMatterNode::new(FrontMatterItem::Null, [value_start, value_start]),
+                }
+            } else {
+                self.lines[self.line].start = line.end - content.trim_start().len();
+                self.inline_value(Some(indent))?
+            };
+            entries.push((key, value));
+        }
+        let end = entries
+            .last()
+            .map_or(start, |(k, v)| v.position[1].max(k.1[1]));
+        Ok(FrontMatterNode::new(
+            FrontMatterItem::Object(entries),
+            [start, end],
+        ))
+    }
+
+    /// Reads the key of the mapping entry the given line starts with, returns the key and
+    /// the offset after its `:`, None if the line does not start with a mapping entry
+    fn mapping_key(&mut self, line: Line) -> Result<Option<(ParsedItem, usize)>, Problem> {
+        let content = &self.text[line.start..line.end];
+        if content.starts_with(['"', '\'']) {
+            self.pos = line.start;
+            let key = self.flow_value()?;
+            let FrontMatterItem::String(value) = key.item else {
+                return Ok(None);
+            };
+            if key.position[1] > line.end {
+                return Ok(None);
+            }
+            let rest = self.text[key.position[1]..line.end].trim_start_matches([' ', '\t']);
+            return Ok(rest
+                .strip_prefix(':')
+                .filter(|v| v.is_empty() || v.starts_with([' ', '\t']))
+                .map(|v| (ParsedItem(value, key.position), line.end - v.len())));
+        }
+        if content.starts_with(['[', '{', '&', '*', '!', '|', '>', '%', '@', '`', '#']) {
+            return Ok(None);
+        }
+        for (i, c) in content.char_indices() {
+            if c == '#' && content[..i].ends_with([' ', '\t']) {
+                break;
+            }
+            if c != ':' {
+                continue;
+            }
+            let rest = &content[i + 1..];
+            if rest.is_empty() || rest.starts_with([' ', '\t']) {
+                let key = content[..i].trim_end();
+                if key.is_empty() {
+                    break;
+                }
+                return Ok(Some((
+                    ParsedItem(key.to_owned(), [line.start, line.start + key.len()]),
+                    line.start + i + 1,
+                )));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Parses a scalar or a flow collection that starts at the current line
+    fn inline_value(&mut self, parent: Option<usize>) -> Result<FrontMatterNode, Problem> {
+        let line = self.lines[self.line];
+        let content = &self.text[line.start..line.end];
+        if content.starts_with(['|', '>']) {
+            return self.block_scalar(parent);
+        }
+        if !content.starts_with(['[', '{', '"', '\'', '&', '*', '!', '%', '@', '`', '?']) {
+            return self.plain_scalar(parent);
+        }
+        self.pos = line.start;
+        let node = self.flow_value()?;
+        let index = self.lines.partition_point(|v| v.begin <= self.pos) - 1;
+        let end = self.lines[index].end;
+        let rest = &self.text[self.pos..end];
+        if !is_blank(rest) {
+            return Err(error(
+                "unexpected characters after value",
+                [end - rest.trim_start().len(), end],
+            ));
+        }
+        self.line = index + 1;
+        Ok(node)
+    }
+
+    /// Parses a plain scalar that starts at the current line and its continuation lines
+    fn plain_scalar(&mut self, parent: Option<usize>) -> Result<FrontMatterNode, Problem> {
+        let start = self.lines[self.line].start;
+        let mut value = String::new();
+        let mut end = start;
+        let mut is_first = true;
+        while let Some(line) = self.lines.get(self.line).copied() {
+            if !is_first
+                && (is_blank(&self.text[line.start..line.end])
+                    || parent.is_some_and(|v| line.indent() <= v))
+            {
+                break;
+            }
+            let content = &self.text[line.start..line.end];
+            let comment = content
+                .char_indices()
+                .find(|(i, c)| *c == '#' && content[..*i].ends_with([' ', '\t']))
+                .map(|(i, _)| i);
+            let part = content[..comment.unwrap_or(content.len())].trim_end();
+            if let Some(i) = part
+                .find(": ")
+                .or_else(|| part.strip_suffix(':').map(|v| v.len()))
+            {
+                return Err(error(
+                    "mapping values are not allowed here",
+                    [line.start + i, line.start + i + 1],
+                ));
+            }
+            if !value.is_empty() {
+                value.push(' ');
+            }
+            value.push_str(part.trim_start());
+            end = line.start + part.len();
+            is_first = false;
+            self.line += 1;
+            if comment.is_some() {
+                break;
+            }
+        }
+        Ok(FrontMatterNode::new(resolve_plain(&value), [start, end]))
+    }
+
+    /// Parses a `|` or `>` block scalar that starts at the current line
+    fn block_scalar(&mut self, parent: Option<usize>) -> Result<FrontMatterNode, Problem> {
+        let line = self.lines[self.line];
+        let header = &self.text[line.start..line.end];
+        let folded = header.starts_with('>');
+        let (chomp, rest) = match header[1..].chars().next() {
+            Some(c @ ('-' | '+')) => (Some(c), &header[2..]),
+            _ => (None, &header[1..]),
+        };
+        if !(rest.is_empty() || rest.starts_with([' ', '\t']) && is_blank(rest)) {
+            return Err(error(
+                "unsupported block scalar header",
+                [line.start, line.end],
+            ));
+        }
+        let min_indent = parent.map_or(0, |v| v + 1);
+        let mut content_indent = None;
+        let mut lines: Vec<&str> = vec![];
+        let mut end = line.end;
+        self.line += 1;
+        while let Some(l) = self.lines.get(self.line) {
+            let indent = l.indent();
+            if self.text[l.begin..l.end].trim().is_empty() {
+                lines.push("");
+            } else if indent < min_indent || content_indent.is_some_and(|v| indent < v) {
+                break;
+            } else {
+                let content_indent = *content_indent.get_or_insert(indent);
+                lines.push(&self.text[l.begin + content_indent..l.end]);
+                end = l.end;
+            }
+            self.line += 1;
+        }
+        let trailing = lines.iter().rev().take_while(|v| v.is_empty()).count();
+        lines.truncate(lines.len() - trailing);
+
+        let mut value = String::new();
+        if folded {
+            for (i, l) in lines.iter().enumerate() {
+                if l.is_empty() {
+                    value.push('\n');
+                } else {
+                    if i > 0 && !lines[i - 1].is_empty() {
+                        value.push(' ');
+                    }
+                    value.push_str(l);
+                }
+            }
+        } else {
+            value = lines.join("\n");
+        }
+        match chomp {
+            Some('-') => {}
+            Some(_) => value.push_str(&"\n".repeat(trailing + 1)),
+            None if !value.is_empty() => value.push('\n'),
+            None => {}
+        }
+        Ok(FrontMatterNode::new(
+            FrontMatterItem::String(value),
+            [line.start, end],
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let is_next = self.peek() == Some(c);
+        if is_next {
+            self.pos += c.len_utf8();
+        }
+        is_next
+    }
+
+    /// Skips whitespaces, line breaks and comments (YAML only)
+    fn skip_space(&mut self) {
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+            self.pos += rest.len() - trimmed.len();
+            if !self.json && trimmed.starts_with('#') {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Builds an error at the current char
+    fn unexpected(&self, msg: &str) -> Problem {
+        let len = self.peek().map_or(0, |v| v.len_utf8());
+        error(msg, [self.pos, self.pos + len])
+    }
+
+    /// Parses a flow node (or a JSON value) at the current offset
+    fn flow_value(&mut self) -> Result<FrontMatterNode, Problem> {
+        self.skip_space();
+        match self.peek() {
+            Some('{') => self.flow_mapping(),
+            Some('[') => self.flow_sequence(),
+            Some('"') => self.quoted('"'),
+            Some('\'') if !self.json => self.quoted('\''),
+            Some('&' | '*' | '!') if !self.json => {
+                Err(self.unexpected("anchors, aliases and tags are not supported"))
+            }
+            Some('%' | '@' | '`' | '?') if !self.json => {
+                Err(self.unexpected("unsupported YAML syntax"))
+            }
+            Some(c) if !self.json && !matches!(c, ',' | ']' | '}' | ':') => {
+                let (value, position) = self.flow_plain();
+                Ok(FrontMatterNode::new(resolve_plain(&value), position))
+            }
+            Some('t' | 'f' | 'n' | '-' | '0'..='9') if self.json => self.json_literal(),
+            _ => Err(self.unexpected("expected a value")),
+        }
+    }
+
+    fn flow_sequence(&mut self) -> Result<FrontMatterNode, Problem> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut items = vec![];
+        self.skip_space();
+        if !self.eat(']') {
+            loop {
+                items.push(self.flow_value()?);
+                self.skip_space();
+                if self.eat(']') {
+                    break;
+                }
+                let comma = self.pos;
+                if !self.eat(',') {
+                    return Err(self.unexpected("expected ',' or ']'"));
+                }
+                self.skip_space();
+                if self.peek() == Some(']') {
+                    if self.json {
+                        return Err(error("trailing comma", [comma, comma + 1]));
+                    }
+                    self.pos += 1;
+                    break;
+                }
+            }
+        }
+        Ok(FrontMatterNode::new(
+            FrontMatterItem::Array(items),
+            [start, self.pos],
+        ))
+    }
+
+    fn flow_mapping(&mut self) -> Result<FrontMatterNode, Problem> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut entries: Vec<(ParsedItem, FrontMatterNode)> = vec![];
+        self.skip_space();
+        if !self.eat('}') {
+            loop {
+                self.skip_space();
+                let key = match self.peek() {
+                    Some('"') => self.quoted('"')?,
+                    Some('\'') if !self.json => self.quoted('\'')?,
+                    Some(c) if !self.json && !matches!(c, ',' | ']' | '}' | ':') => {
+                        let (value, position) = self.flow_plain();
+                        FrontMatterNode::new(FrontMatterItem::String(value), position)
+                    }
+                    _ => return Err(self.unexpected("expected a string key")),
+                };
+                let FrontMatterItem::String(key_value) = key.item else {
+                    return Err(error("expected a string key", key.position));
+                };
+                if entries.iter().any(|(k, _)| k.0 == key_value) {
+                    return Err(error(
+                        &format!("duplicate key: {}", key_value),
+                        key.position,
+                    ));
+                }
+                self.skip_space();
+                if !self.eat(':') {
+                    return Err(self.unexpected("expected ':'"));
+                }
+                let value = self.flow_value()?;
+                entries.push((ParsedItem(key_value, key.position), value));
+                self.skip_space();
+                if self.eat('}') {
+                    break;
+                }
+                let comma = self.pos;
+                if !self.eat(',') {
+                    return Err(self.unexpected("expected ',' or '}'"));
+                }
+                self.skip_space();
+                if self.peek() == Some('}') {
+                    if self.json {
+                        return Err(error("trailing comma", [comma, comma + 1]));
+                    }
+                    self.pos += 1;
+                    break;
+                }
+            }
+        }
+        Ok(FrontMatterNode::new(
+            FrontMatterItem::Object(entries),
+            [start, self.pos],
+        ))
+    }
+
+    /// Reads a plain scalar inside of a flow collection
+    fn flow_plain(&mut self) -> (String, Offsets) {
+        let start = self.pos;
+        let rest = &self.text[start..];
+        let mut len = rest.len();
+        for (i, c) in rest.char_indices() {
+            let next = rest[i + c.len_utf8()..].chars().next();
+            let is_end = match c {
+                ',' | '[' | ']' | '{' | '}' | '\n' | '\r' => true,
+                '#' => rest[..i].ends_with([' ', '\t']),
+                ':' => next.is_none_or(|v| " \t\r\n,[]{}".contains(v)),
+                _ => false,
+            };
+            if is_end {
+                len = i;
+                break;
+            }
+        }
+        let value = rest[..len].trim_end();
+        self.pos = start + value.len();
+        (value.to_owned(), [start, self.pos])
+    }
+
+    /// Parses a double quoted (or JSON) or single quoted scalar, line breaks inside of YAML
+    /// quoted scalars are folded
+    fn quoted(&mut self, quote: char) -> Result<FrontMatterNode, Problem> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let at = self.pos;
+            let Some(c) = self.peek() else {
+                return Err(error("unterminated string", [start, self.pos]));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '\'' if quote == '\'' && self.peek() == Some('\'') => {
+                    self.pos += 1;
+                    value.push('\'');
+                }
+                c if c == quote => break,
+                '\\' if quote == '"' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(error("unterminated string", [start, self.pos]));
+                    };
+                    self.pos += escape.len_utf8();
+                    match escape {
+                        '"' | '\\' | '/' => value.push(escape),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        '0' if !self.json => value.push('\0'),
+                        'u' => value.push(self.unicode_escape(at)?),
+                        _ => return Err(error("invalid escape sequence", [at, self.pos])),
+                    }
+                }
+                '\n' | '\r' if !self.json => {
+                    let rest = &self.text[self.pos..];
+                    let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+                    let breaks = rest[..rest.len() - trimmed.len()].matches('\n').count()
+                        + usize::from(c == '\n');
+                    self.pos += rest.len() - trimmed.len();
+                    value.truncate(value.trim_end_matches([' ', '\t']).len());
+                    if breaks > 1 {
+                        value.push_str(&"\n".repeat(breaks - 1));
+                    } else {
+                        value.push(' ');
+                    }
+                }
+                c if self.json && (c as u32) < 0x20 => {
+                    return Err(error("control character in string", [at, self.pos]));
+                }
+                c => value.push(c),
+            }
+        }
+        Ok(FrontMatterNode::new(
+            FrontMatterItem::String(value),
+            [start, self.pos],
+        ))
+    }
+
+    /// Reads the 4 hex digits of a `\u` escape and its low surrogate pair if there is one
+    fn unicode_escape(&mut self, at: usize) -> Result<char, Problem> {
+        let code = match self.hex_digits() {
+            Some(high @ 0xD800..=0xDBFF) if self.text[self.pos..].starts_with("\\u") => {
+                self.pos += 2;
+                self.hex_digits()
+                    .filter(|v| (0xDC00..=0xDFFF).contains(v))
+                    .map(|low| 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            }
+            v => v,
+        };
+        code.and_then(char::from_u32)
+            .ok_or(error("invalid unicode escape", [at, self.pos]))
+    }
+
+    fn hex_digits(&mut self) -> Option<u32> {
+        let digits = self.text.get(self.pos..self.pos + 4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    /// Reads a JSON number, `true`, `false` or `null`
+    fn json_literal(&mut self) -> Result<FrontMatterNode, Problem> {
+        let start = self.pos;
+        let rest = &self.text[start..];
+        for (word, item) in [
+            ("true", FrontMatterItem::Bool(true)),
+            ("false", FrontMatterItem::Bool(false)),
+            ("null", FrontMatterItem::Null),
+        ] {
+            if rest.starts_with(word) {
+                self.pos += word.len();
+                return Ok(FrontMatterNode::new(item, [start, self.pos]));
+            }
+        }
+        let len = rest
+            .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            .unwrap_or(rest.len());
+        match rest[..len].parse::<Number>() {
+            Ok(number) if len > 0 => {
+                self.pos += len;
+                Ok(FrontMatterNode::new(
+                    FrontMatterItem::Number(number),
+                    [start, self.pos],
+                ))
+            }
+            _ => Err(error("invalid number", [start, start + len.max(1)])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use proptest::prelude::*;
+
+    fn value(text: &str) -> Option<Value> {
+        parse_front_matter(text).unwrap().map(|v| v.to_value())
+    }
+
+    #[test]
+    fn test_parse_yaml_front_matter() {
+        let text = r#"# deployment config
+name: my-order
+version: 2
+ratio: 1.5e2
+enabled: true
+empty:
+tilde: ~
+address: 0x1234
+big: 123456789012345678901234567890
+quoted: "a \"b\"\n"
+single: 'it''s'
+url: https://rainlang.xyz # a comment
+entrypoints:
+  - calculate-io
+  - handle-io
+tokens: [usdt, { symbol: weth, decimals: 18 }]
+orders:
+- owner: alice
+  inputs: [ ]
+- - 1
+  - 2
+nested:
+  deep:
+    key: value
+    folded: this is
+      a long text
+literal: |
+  line 1
+   line 2
+
+folded: >-
+  a
+  b
+json: {"a": [1, 2], 'b': null}
+"#;
+        assert_eq!(
+            value(text),
+            Some(json!({
+                "name": "my-order",
+                "version": 2,
+                "ratio": 150.0,
+                "enabled": true,
+                "empty": null,
+                "tilde": null,
+                "address": "0x1234",
+                "big": "123456789012345678901234567890",
+                "quoted": "a \"b\"\n",
+                "single": "it's",
+                "url": "https://rainlang.xyz",
+                "entrypoints": ["calculate-io", "handle-io"],
+                "tokens": ["usdt", { "symbol": "weth", "decimals": 18 }],
+                "orders": [{ "owner": "alice", "inputs": [] }, [1, 2]],
+                "nested": { "deep": { "key": "value", "folded": "this is a long text" } },
+                "literal": "line 1\n line 2\n",
+                "folded": "a b",
+                "json": { "a": [1, 2], "b": null },
+            }))
+        );
+
+        assert_eq!(value(""), None);
+        assert_eq!(value(" \n# only a comment\n"), None);
+        assert_eq!(
+            value("some front matter\ntext"),
+            Some(json!("some front matter text"))
+        );
+        assert_eq!(value("- a\n-\n- 'b'"), Some(json!(["a", null, "b"])));
+
+        let node = parse_front_matter("a:\n  b: [1, \"c\"]\n")
+            .unwrap()
+            .unwrap();
+        let FrontMatterItem::Object(entries) = &node.item else {
+            panic!("expected an object");
+        };
+        assert_eq!(node.position, [0, 16]);
+        assert_eq!(entries[0].0, ParsedItem("a".to_owned(), [0, 1]));
+        let FrontMatterItem::Object(entries) = &entries[0].1.item else {
+            panic!("expected an object");
+        };
+        assert_eq!(entries[0].0, ParsedItem("b".to_owned(), [5, 6]));
+        assert_eq!(entries[0].1.position, [8, 16]);
+    }
+
+    #[test]
+    fn test_parse_json_front_matter() {
+        let text = r#"
+{
+  "name": "my-order",
+  "values": [1, -2.5, 1e3, true, false, null],
+  "escaped": "\u00e9\ud834\udd1e\t"
+}
+"#;
+        assert_eq!(
+            value(text),
+            Some(json!({
+                "name": "my-order",
+                "values": [1, -2.5, 1000.0, true, false, null],
+                "escaped": "é𝄞\t",
+            }))
+        );
+        assert_eq!(value("[]"), Some(json!([])));
+    }
+
+    #[test]
+    fn test_front_matter_errors() {
+        let cases = [
+            (r#"{"a": 1,}"#, "trailing comma", [7, 8]),
+            (r#"{"a": 1 "b": 2}"#, "expected ',' or '}'", [8, 9]),
+            (r#"{a: 1}"#, "expected a string key", [1, 2]),
+            (r#"{"a": 01}"#, "invalid number", [6, 8]),
+            (r#"["a" "#, "expected ',' or ']'", [5, 5]),
+            ("[1] 2", "unexpected characters after value", [4, 5]),
+            (r#"{"a": "b\q"}"#, "invalid escape sequence", [8, 10]),
+            ("a: 1\na: 2", "duplicate key: a", [5, 6]),
+            (
+                "a: 1\n  b: 2",
+                "mapping values are not allowed here",
+                [8, 9],
+            ),
+            (
+                "a:\n  b: 1\n c: 2",
+                "bad indentation of a mapping entry",
+                [11, 15],
+            ),
+            ("a: 1\n- b", "expected a mapping entry", [5, 8]),
+            ("a: [1, 2\nb: 3", "expected ',' or ']'", [9, 10]),
+            ("a: \"b", "unterminated string", [3, 5]),
+            (
+                "a: &x 1",
+                "anchors, aliases and tags are not supported",
+                [3, 4],
+            ),
+            (
+                "a: !!str 1",
+                "anchors, aliases and tags are not supported",
+                [3, 4],
+            ),
+            ("a: [1] b", "unexpected characters after value", [7, 8]),
+            ("a:\n\tb: 1", "tabs are not allowed in indentation", [3, 4]),
+            ("a: |2\n  b", "unsupported block scalar header", [3, 5]),
+            ("- a\nb", "unexpected content", [4, 5]),
+        ];
+        for (text, msg, position) in cases {
+            assert_eq!(
+                parse_front_matter(text),
+                Err(Problem {
+                    msg: format!("invalid front matter: {}", msg),
+                    position,
+                    code: ErrorCode::InvalidFrontMatter,
+                    severity: Severity::Error,
+                    origin: None,
+                }),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_front_matter() -> anyhow::Result<()> {
+        let schema: FrontMatterSchema = serde_json::from_str(
+            r#"{
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "required": ["name", "network"],
+                "additionalProperties": false,
+                "properties": {
+                    "name": { "type": "string", "pattern": "^[a-z-]+$" },
+                    "network": { "enum": ["mainnet", "polygon"] },
+                    "chain-id": { "type": ["integer", "null"], "minimum": 1 },
+                    "entrypoints": { "type": "array", "items": { "type": "string" } }
+                }
+            }"#,
+        )?;
+        let text = "name: My Order\nchain-id: 0\nentrypoints: [a, 1]\nowner: alice\n---\n#a 1";
+        let mut rain_document = RainDocument::new(text.to_owned(), None, 0, None);
+        rain_document.set_front_matter_schema(Some(schema.clone()));
+        futures::executor::block_on(rain_document.parse(false, None));
+        let expected = vec![
+            ErrorCode::InvalidFrontMatterValue
+                .to_problem(vec!["/", "missing required property network"], [0, 59]),
+            ErrorCode::InvalidFrontMatterValue.to_problem(
+                vec!["/name", "expected a string matching ^[a-z-]+$"],
+                [6, 14],
+            ),
+            ErrorCode::InvalidFrontMatterValue
+                .to_problem(vec!["/chain-id", "expected a number >= 1"], [25, 26]),
+            ErrorCode::InvalidFrontMatterValue.to_problem(
+                vec!["/entrypoints/1", "expected string, found number"],
+                [44, 45],
+            ),
+            ErrorCode::InvalidFrontMatterValue
+                .to_problem(vec!["/", "unknown property owner"], [47, 52]),
+        ];
+        assert_eq!(rain_document.problems, expected);
+        assert_eq!(
+            rain_document.front_matter_value(),
+            Some(json!({
+                "name": "My Order",
+                "chain-id": 0,
+                "entrypoints": ["a", 1],
+                "owner": "alice",
+            }))
+        );
+
+        rain_document.update("name: [a\n---\n#a 1".to_owned(), None);
+        assert_eq!(
+            rain_document.problems,
+            vec![ErrorCode::InvalidFrontMatter.to_problem(vec!["expected ',' or ']'"], [9, 9])]
+        );
+        assert_eq!(rain_document.front_matter_value(), None);
+
+        // problems are only reported when there is a schema, setting it revalidates right away
+        rain_document.set_front_matter_schema(None);
+        assert!(rain_document.problems.is_empty());
+        rain_document.set_front_matter_schema(Some(schema));
+        assert_eq!(
+            rain_document.problems,
+            vec![ErrorCode::InvalidFrontMatter.to_problem(vec!["expected ',' or ']'"], [9, 9])]
+        );
+
+        let schema: FrontMatterSchema = serde_json::from_str(r#"{"type": "object"}"#)?;
+        assert_eq!(
+            schema.validate_front_matter(""),
+            vec![ErrorCode::InvalidFrontMatterValue
+                .to_problem(vec!["/", "expected object, found null"], [0, 0])]
+        );
+
+        let result = serde_json::from_str::<FrontMatterSchema>(
+            r#"{"properties": {"name": {"type": "string", "pattern": "[a-z"}}}"#,
+        );
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid front matter schema pattern [a-z: "));
+        assert!(FrontMatterPattern::new("[a-z").is_err());
+        assert_eq!(
+            serde_json::to_string(&FrontMatterPattern::new("^[a-z-]+$")?)?,
+            r#""^[a-z-]+$""#
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_arbitrary_front_matter(text in "[ab1:#|>\\-'\"\\[\\]{},&\t\n é]{0,60}") {
+            if let Ok(Some(node)) = parse_front_matter(&text) {
+                prop_assert!(node.position[0] <= node.position[1]);
+                prop_assert!(node.position[1] <= text.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Single pass tokenizer of RainDocument and rainlang texts
+
+use super::super::types::{
+    ast::{Offsets, ParsedItem},
+    patterns::PRAGMA_KEYWORD,
+};
+
+/// Kind of a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// a run of whitespaces
+    Whitespace,
+    /// a comment, which might not be ended
+    Comment,
+    /// `@`, start of an import statement
+    At,
+    /// `#`, start of a binding
+    Hash,
+    /// `;`, end of a rainlang source
+    Semi,
+    /// `,`, end of a rainlang sub source
+    Comma,
+    /// a run of any other characters
+    Word,
+}
+
+/// Type of a token of a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: Offsets,
+}
+
+/// Tokenizes the given text in a single pass, offset is applied to the tokens positions
+pub fn tokenize(text: &str, offset: usize) -> Vec<Token> {
+    let bytes = text.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = text[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+                TokenKind::Comment
+            }
+            b'@' => {
+                i += 1;
+                TokenKind::At
+            }
+            b'#' => {
+                i += 1;
+                TokenKind::Hash
+            }
+            b';' => {
+                i += 1;
+                TokenKind::Semi
+            }
+            b',' => {
+                i += 1;
+                TokenKind::Comma
+            }
+            _ => {
+                let is_whitespace = starts_with_whitespace(&text[i..]);
+                while i < bytes.len() {
+                    let c = text[i..].chars().next().unwrap();
+                    if is_whitespace != c.is_whitespace()
+                        || (!is_whitespace
+                            && (matches!(c, '@' | '#' | ';' | ',') || text[i..].starts_with("/*")))
+                    {
+                        break;
+                    }
+                    i += c.len_utf8();
+                }
+                if is_whitespace {
+                    TokenKind::Whitespace
+                } else {
+                    TokenKind::Word
+                }
+            }
+        };
+        tokens.push(Token {
+            kind,
+            position: [start + offset, i + offset],
+        });
+    }
+    tokens
+}
+
+/// Finds the positions of the pragma keywords of the given tokens of a text, ie the keyword
+/// words that are separated from their neighbours by whitespaces or comments
+pub fn pragma_keywords(text: &str, tokens: &[Token]) -> Vec<Offsets> {
+    let is_separator = |v: Option<&Token>| {
+        v.is_none_or(|v| matches!(v.kind, TokenKind::Whitespace | TokenKind::Comment))
+    };
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, v)| {
+            v.kind == TokenKind::Word
+                && text[v.position[0]..v.position[1]] == *PRAGMA_KEYWORD
+                && is_separator(i.checked_sub(1).map(|j| &tokens[j]))
+                && is_separator(tokens.get(i + 1))
+        })
+        .map(|(_, v)| v.position)
+        .collect()
+}
+
+/// Finds all the runs of illegal characters (non printable ASCII and non whitespace) in the given text
+/// starting at the given offset, comments and string literals are skipped as they can contain any character
+pub fn illegal_code_chars(text: &str, offset: usize) -> Vec<ParsedItem> {
+    let mut runs = vec![];
+    // a string literal spans over whitespaces but not over delimiters
+    let mut in_string = false;
+    let mut escaped = false;
+    for token in tokenize(&text[offset..], offset) {
+        match token.kind {
+            TokenKind::Word => {
+                let mut start = None;
+                for (i, c) in text[token.position[0]..token.position[1]].char_indices() {
+                    let i = i + token.position[0];
+                    if escaped {
+                        escaped = false;
+                    } else if in_string && c == '\\' {
+                        escaped = true;
+                    } else if c == '"' {
+                        in_string = !in_string;
+                    }
+                    match (start, !in_string && is_illegal(c)) {
+                        (None, true) => start = Some(i),
+                        (Some(s), false) => {
+                            runs.push(ParsedItem(text[s..i].to_owned(), [s, i]));
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(s) = start {
+                    let end = token.position[1];
+                    runs.push(ParsedItem(text[s..end].to_owned(), [s, end]));
+                }
+            }
+            TokenKind::Whitespace | TokenKind::Comment => {}
+            _ => {
+                in_string = false;
+                escaped = false;
+            }
+        }
+    }
+    runs
+}
+
+/// Splits the given text into its non whitespace runs, offset is applied to the runs positions
+pub fn split_whitespace(text: &str, offset: usize) -> Vec<ParsedItem> {
+    let mut items = vec![];
+    let mut start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                items.push(ParsedItem(text[s..i].to_owned(), [s + offset, i + offset]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Splits the given range of a text at the given delimiter tokens positions
+pub fn split_at(text: &str, range: Offsets, delimiters: &[Offsets]) -> Vec<ParsedItem> {
+    let mut items = vec![];
+    let mut start = range[0];
+    for delimiter in delimiters {
+        items.push(ParsedItem(
+            text[start..delimiter[0]].to_owned(),
+            [start, delimiter[0]],
+        ));
+        start = delimiter[1];
+    }
+    items.push(ParsedItem(
+        text[start..range[1]].to_owned(),
+        [start, range[1]],
+    ));
+    items
+}
+
+/// Returns the given range of a text with the given sub ranges replaced by whitespaces (keeping
+/// the line structure and byte offsets intact) as well as the rest of the range after the given `tail` offset
+pub(crate) fn blank(text: &str, range: Offsets, holes: &[Offsets], tail: Option<usize>) -> String {
+    let first = holes.partition_point(|v| v[1] <= range[0]);
+    let mut holes = holes[first..].iter().peekable();
+    text[range[0]..range[1]]
+        .char_indices()
+        .map(|(i, c)| {
+            let i = i + range[0];
+            while holes.next_if(|v| v[1] <= i).is_some() {}
+            let is_hole = holes.peek().is_some_and(|v| v[0] <= i) || tail.is_some_and(|v| v <= i);
+            if is_hole && !c.is_whitespace() {
+                " ".repeat(c.len_utf8())
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Determines if the given char is illegal, ie non printable ASCII and non whitespace
+fn is_illegal(c: char) -> bool {
+    !(' '..='~').contains(&c) && !c.is_whitespace()
+}
+
+fn starts_with_whitespace(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{exclusive_parse, fill_in, inclusive_parse, split_dotrain, DotrainSections};
+    use crate::types::patterns::*;
+    use proptest::prelude::*;
+
+    /// the regex based passes that the lexer replaced
+    fn regex_split_dotrain(text: &str, body_start: usize) -> DotrainSections {
+        let mut document = text.to_owned();
+        fill_in(&mut document, [0, body_start]).unwrap();
+        let mut sections = DotrainSections::default();
+        for comment in inclusive_parse(&document, &COMMENT_PATTERN, 0) {
+            fill_in(&mut document, comment.1).unwrap();
+            sections.comments.push(comment);
+        }
+        for mut statement in exclusive_parse(&document, &IMPORTS_PATTERN, 0, true)
+            .into_iter()
+            .skip(1)
+        {
+            if let Some(index) = statement.0.find('#') {
+                statement.0.truncate(index);
+                statement.1[1] = statement.1[0] + index;
+            }
+            fill_in(&mut document, [statement.1[0] - 1, statement.1[1]]).unwrap();
+            sections.imports.push(statement);
+        }
+        for binding in exclusive_parse(&document, &BINDING_PATTERN, 0, true)
+            .into_iter()
+            .skip(1)
+        {
+            fill_in(&mut document, [binding.1[0] - 1, binding.1[1]]).unwrap();
+            sections.bindings.push(binding);
+        }
+        sections.unexpected = exclusive_parse(&document, &WS_PATTERN, 0, false)
+            .into_iter()
+            .map(|v| v.1)
+            .collect();
+        sections
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let text = "@a 0x1\n#b /* c # */ 1;x,y /* open";
+        let kinds = tokenize(text, 0)
+            .iter()
+            .map(|v| (v.kind, &text[v.position[0]..v.position[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenKind::At, "@"),
+                (TokenKind::Word, "a"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Word, "0x1"),
+                (TokenKind::Whitespace, "\n"),
+                (TokenKind::Hash, "#"),
+                (TokenKind::Word, "b"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Comment, "/* c # */"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Word, "1"),
+                (TokenKind::Semi, ";"),
+                (TokenKind::Word, "x"),
+                (TokenKind::Comma, ","),
+                (TokenKind::Word, "y"),
+                (TokenKind::Whitespace, " "),
+                (TokenKind::Comment, "/* open"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_illegal_char_and_split_whitespace() {
+        assert_eq!(
+            illegal_code_chars("abc ∂∂ d ∂", 0),
+            vec![
+                ParsedItem("∂∂".to_owned(), [4, 10]),
+                ParsedItem("∂".to_owned(), [13, 16]),
+            ]
+        );
+        assert!(illegal_code_chars("abc \u{a0} d", 0).is_empty());
+        assert_eq!(
+            illegal_code_chars("∂ /* ∂ */ \"∂ ∂\" a∂b \"∂", 3),
+            vec![ParsedItem("∂".to_owned(), [25, 28])]
+        );
+        assert_eq!(
+            illegal_code_chars("\"∂, ∂ ∂é;∂", 0),
+            vec![
+                ParsedItem("∂".to_owned(), [6, 9]),
+                ParsedItem("∂é".to_owned(), [10, 15]),
+                ParsedItem("∂".to_owned(), [16, 19]),
+            ]
+        );
+        assert_eq!(
+            illegal_code_chars("\"\\\" ∂\" ∂", 0),
+            vec![ParsedItem("∂".to_owned(), [9, 12])]
+        );
+        assert_eq!(blank("a∂b", [0, 5], &[[1, 4]], None), "a   b");
+        assert_eq!(
+            split_whitespace(" ab  c\n", 2),
+            vec![
+                ParsedItem("ab".to_owned(), [3, 5]),
+                ParsedItem("c".to_owned(), [7, 8])
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_split_dotrain_matches_regex_passes(
+            text in "[ab/*@#;,:\\- \n]{0,60}",
+            body_start in 0usize..10,
+        ) {
+            let body_start = body_start.min(text.len());
+            prop_assert_eq!(
+                split_dotrain(&text, body_start),
+                regex_split_dotrain(&text, body_start)
+            );
+            prop_assert_eq!(
+                split_whitespace(&text, 3),
+                exclusive_parse(&text, &WS_PATTERN, 3, false)
+            );
+        }
+    }
+}
@@ -6,11 +6,18 @@ use super::types::{
     patterns::{NAMESPACE_SEGMENT_PATTERN, WORD_PATTERN},
 };
 
+pub(crate) mod cst;
+pub(crate) mod frontmatter;
+pub(crate) mod lexer;
 pub(crate) mod raindocument;
 pub(crate) mod rainlangdocument;
 
+pub use self::cst::{dotrain_cst, rainlang_cst};
+pub use self::frontmatter::*;
 pub use self::raindocument::*;
 pub use self::rainlangdocument::*;
+pub(crate) use self::cst::*;
+pub(crate) use self::lexer::*;
 
 /// Parses an string by extracting matching strings.
 pub fn inclusive_parse(text: &str, pattern: &Regex, offset: usize) -> Vec<ParsedItem> {
@@ -70,7 +77,7 @@ pub fn exclusive_parse(
     result
 }
 
-/// Fills a poistion in a text with whitespaces by keeping line structure intact
+/// Fills a poistion in a text with whitespaces by keeping line structure and byte offsets intact
 pub fn fill_in(text: &mut String, position: Offsets) -> Result<(), Error> {
     text.replace_range(
         position[0]..position[1],
@@ -78,13 +85,19 @@ pub fn fill_in(text: &mut String, position: Offsets) -> Result<(), Error> {
             .get(position[0]..position[1])
             .ok_or(Error::OutOfCharBoundry)?
             .chars()
-            .map(|c| if c.is_whitespace() { c } else { ' ' })
+            .map(|c| {
+                if c.is_whitespace() {
+                    c.to_string()
+                } else {
+                    " ".repeat(c.len_utf8())
+                }
+            })
             .collect::<String>(),
     );
     Ok(())
 }
 
-/// Fills a text with whitespaces excluding a position by keeping line structure intact
+/// Fills a text with whitespaces excluding a position by keeping line structure and byte offsets intact
 pub fn fill_out(text: &mut String, position: Offsets) -> Result<(), Error> {
     text.replace_range(
         ..position[0],
@@ -92,7 +105,13 @@ pub fn fill_out(text: &mut String, position: Offsets) -> Result<(), Error> {
             .get(..position[0])
             .ok_or(Error::OutOfCharBoundry)?
             .chars()
-            .map(|c| if c.is_whitespace() { c } else { ' ' })
+            .map(|c| {
+                if c.is_whitespace() {
+                    c.to_string()
+                } else {
+                    " ".repeat(c.len_utf8())
+                }
+            })
             .collect::<String>(),
     );
     text.replace_range(
@@ -101,7 +120,13 @@ pub fn fill_out(text: &mut String, position: Offsets) -> Result<(), Error> {
             .get(position[1]..)
             .ok_or(Error::OutOfCharBoundry)?
             .chars()
-            .map(|c| if c.is_whitespace() { c } else { ' ' })
+            .map(|c| {
+                if c.is_whitespace() {
+                    c.to_string()
+                } else {
+                    " ".repeat(c.len_utf8())
+                }
+            })
             .collect::<String>(),
     );
     Ok(())
@@ -253,6 +278,81 @@ pub fn parse_cli_key_val(
     ))
 }
 
+/// Parses a matrix of rebinds where each row is a rebind variant, the matrix can be either a json
+/// array of objects that map keys to values, or a csv whose header row holds the keys and each of
+/// its following rows holds the values of a variant, csv fields can be double quoted
+pub fn parse_rebind_matrix(
+    text: &str,
+) -> Result<Vec<Vec<Rebind>>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    if text.trim_start().starts_with('[') {
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(text)?;
+        return Ok(rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(key, value)| match value {
+                        serde_json::Value::String(v) => Rebind(key, v),
+                        v => Rebind(key, v.to_string()),
+                    })
+                    .collect()
+            })
+            .collect());
+    }
+    let mut lines = text.lines().filter(|v| !v.trim().is_empty());
+    let keys = parse_csv_row(lines.next().ok_or("empty rebind matrix")?)?;
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let values = parse_csv_row(line)?;
+            if values.len() != keys.len() {
+                return Err(format!(
+                    "row {} has {} values, expected {}",
+                    i + 1,
+                    values.len(),
+                    keys.len()
+                )
+                .into());
+            }
+            Ok(keys
+                .iter()
+                .zip(values)
+                .map(|(key, value)| Rebind(key.trim().to_owned(), value))
+                .collect())
+        })
+        .collect()
+}
+
+/// Parses a csv row into its fields
+fn parse_csv_row(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(format!("unterminated quoted field: {}", line));
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::*;
@@ -343,4 +443,27 @@ mod tests {
         let result = line_number(text, 38);
         assert_eq!(result, 3);
     }
+
+    #[test]
+    fn test_parse_rebind_matrix() {
+        let expected = vec![
+            vec![
+                Rebind("a".to_owned(), "1".to_owned()),
+                Rebind("b".to_owned(), "\" some, string \"".to_owned()),
+            ],
+            vec![
+                Rebind("a".to_owned(), "0x02".to_owned()),
+                Rebind("b".to_owned(), "'imp.exp".to_owned()),
+            ],
+        ];
+
+        let csv = "a,b\n1,\"\"\" some, string \"\"\"\n0x02,'imp.exp\n";
+        assert_eq!(parse_rebind_matrix(csv).unwrap(), expected);
+
+        let json = r#"[{"a": 1, "b": "\" some, string \""}, {"a": "0x02", "b": "'imp.exp"}]"#;
+        assert_eq!(parse_rebind_matrix(json).unwrap(), expected);
+
+        assert!(parse_rebind_matrix("a,b\n1").is_err());
+        assert!(parse_rebind_matrix("a\n\"1").is_err());
+    }
 }
@@ -1,13 +1,19 @@
 use async_recursion::async_recursion;
 use std::collections::{HashMap, VecDeque};
 use futures::future::join_all;
-use rain_metadata::{types::dotrain::v1::DotrainMeta, KnownMagic, RainMetaDocumentV1Item, search};
+use rain_metadata::{
+    types::dotrain::v1::DotrainMeta, KnownMagic, RainMetaDocumentV1Item, search, search_deployer,
+};
 use super::*;
 use super::super::{
-    super::error::{Error, ErrorCode},
-    deep_read_quote, exclusive_parse, fill_in, inclusive_parse, is_consumable,
-    rainlangdocument::RainlangDocument,
-    tracked_trim,
+    super::{
+        error::{Error, ErrorCode},
+        types::literal::{string_literal_end, LiteralValue},
+    },
+    deep_read_quote, exclusive_parse, illegal_code_chars, is_consumable, pragma_keywords,
+    search_binding_ref, split_dotrain, split_whitespace,
+    rainlangdocument::{RainlangDocument, WORD_SPEC_CODES},
+    tokenize, tracked_trim, TokenKind,
 };
 
 impl RainDocument {
@@ -20,40 +26,44 @@ impl RainDocument {
         &mut self,
         remote_search: bool,
         opts_rebinds: Option<Vec<Rebind>>,
+        cache: Option<ParseCache>,
     ) -> Result<(), Error> {
+        let cache = cache.unwrap_or_default();
         self.imports.clear();
         self.problems.clear();
         self.comments.clear();
         self.bindings.clear();
         self.namespace.clear();
-        self.known_words = None;
         self.front_matter_offset = 0;
+        self.rebinds = opts_rebinds.clone();
 
-        let mut document = self.text.clone();
         let mut namespace: Namespace = HashMap::new();
 
-        // check for illegal characters, ends parsing right away if found any
-        let illegal_chars = inclusive_parse(&document, &ILLEGAL_CHAR, 0);
-        if !illegal_chars.is_empty() {
-            self.problems.push(ErrorCode::IllegalChar.to_problem(
-                vec![&illegal_chars[0].0],
-                [illegal_chars[0].1[0], illegal_chars[0].1[0]],
-            ));
-            return Ok(());
+        let splitter = self.text.find(FRONTMATTER_SEPARATOR);
+        let body_start_offset = splitter.map_or(0, |v| v + FRONTMATTER_SEPARATOR.len());
+
+        // check for illegal characters, front matter, comments and string literals can contain
+        // any character, each run of illegal characters is reported and parsing continues with
+        // them left in place, so the items they are part of are parsed as erroneous items
+        for illegal_char in illegal_code_chars(&self.text, body_start_offset) {
+            self.problems
+                .push(ErrorCode::IllegalChar.to_problem(vec![&illegal_char.0], illegal_char.1));
         }
 
         // split front matter and rest of the text
-        if let Some(splitter) = document.find(FRONTMATTER_SEPARATOR) {
+        if let Some(splitter) = splitter {
             self.front_matter_offset = splitter;
-            let body_start_offset = splitter + FRONTMATTER_SEPARATOR.len();
-            fill_in(&mut document, [0, body_start_offset])?;
         } else {
             self.problems
                 .push(ErrorCode::NoFrontMatterSplitter.to_problem(vec![], [0, 0]));
-        };
+        }
+
+        // validate the front matter if there is a schema to validate against
+        self.validate_front_matter();
 
-        // parse comments
-        for parsed_comment in inclusive_parse(&document, &COMMENT_PATTERN, 0).iter() {
+        // read comments, import statements and bindings from the body tokens
+        let sections = split_dotrain(&self.text, body_start_offset);
+        for parsed_comment in &sections.comments {
             // if a comment is not ended
             if !parsed_comment.0.ends_with("*/") {
                 self.problems
@@ -63,28 +73,6 @@ impl RainDocument {
                 comment: parsed_comment.0.clone(),
                 position: parsed_comment.1,
             });
-            fill_in(&mut document, parsed_comment.1)?;
-        }
-
-        // since exclusive_parse() is being used with 'include_empty_ends' arg set to true,
-        // the first item of the parsed items should be ignored since it only contains the
-        // text before the first match
-        // this will apply for parsing imports and bindings
-        let mut ignore_first = true;
-
-        // parse and take out each import statement from the text
-        let mut import_statements = exclusive_parse(&document, &IMPORTS_PATTERN, 0, true);
-        for imp_statement in &mut import_statements {
-            if ignore_first {
-                ignore_first = false;
-                continue;
-            }
-            if let Some(index) = imp_statement.0.find('#') {
-                let slices = imp_statement.0.split_at(index);
-                imp_statement.0 = slices.0.to_owned();
-                imp_statement.1[1] = imp_statement.1[0] + index;
-            };
-            fill_in(&mut document, [imp_statement.1[0] - 1, imp_statement.1[1]])?;
         }
 
         // try to parse import statements if only the current instance isnt an import itself
@@ -92,15 +80,17 @@ impl RainDocument {
         // parsing each import is an async fn as each import might not be cached in the CAS
         // and may need reading from underlying subgraphs, so they are triggered and awaited
         // alltogether with care for read/write lock on the CAS
-        ignore_first = true;
         if self.import_depth < 32 {
+            // imported dotrains are parsed with this instance's words, so they are reused
+            // from the previous parse only if the words are the same
+            let cached_imports: &[Import] = if self.known_words == cache.known_words {
+                &cache.imports
+            } else {
+                &[]
+            };
             let mut futures = vec![];
-            for s in &import_statements {
-                if ignore_first {
-                    ignore_first = false;
-                    continue;
-                }
-                futures.push(self.process_import(s, remote_search));
+            for s in &sections.imports {
+                futures.push(self.process_import(s, remote_search, cached_imports));
             }
             let mut parsed_imports = join_all(futures).await;
 
@@ -121,7 +111,7 @@ impl RainDocument {
             }
             self.imports.extend(parsed_imports);
         } else {
-            for s in import_statements {
+            for s in &sections.imports {
                 self.problems
                     .push(ErrorCode::DeepImport.to_problem(vec![], [s.1[0] - 1, s.1[1]]));
             }
@@ -134,18 +124,8 @@ impl RainDocument {
         }
 
         // parsing bindings
-        let parsed_bindings = exclusive_parse(&document, &BINDING_PATTERN, 0, true);
-        ignore_first = true;
-        for parsed_binding in &parsed_bindings {
-            if ignore_first {
-                ignore_first = false;
-                continue;
-            }
+        for parsed_binding in &sections.bindings {
             self.process_binding(parsed_binding, &mut namespace);
-            fill_in(
-                &mut document,
-                [parsed_binding.1[0] - 1, parsed_binding.1[1]],
-            )?;
         }
 
         // find non-top level imports
@@ -158,6 +138,18 @@ impl RainDocument {
             }
         }
 
+        // rainlang bindings can be reused from the previous parse only if the imports and words
+        // are the same and neither parse had rebinds, as the parsed rainlang depends on the namespace
+        let reusable_bindings = if opts_rebinds.is_none()
+            && cache.rebinds.is_none()
+            && self.known_words == cache.known_words
+            && Self::same_imports(&self.imports, &cache.imports)
+        {
+            Self::reusable_bindings(&self.bindings, &cache.bindings)
+        } else {
+            HashMap::new()
+        };
+
         // apply overrides
         if let Some(rebinds) = opts_rebinds {
             Self::apply_overrides(rebinds, &mut namespace)?;
@@ -170,12 +162,10 @@ impl RainDocument {
         self.validate_quote_bindings();
 
         // find any remaining strings and include them as errors
-        exclusive_parse(&document, &WS_PATTERN, 0, false)
-            .iter()
-            .for_each(|v| {
-                self.problems
-                    .push(ErrorCode::UnexpectedToken.to_problem(vec![], v.1))
-            });
+        for position in sections.unexpected {
+            self.problems
+                .push(ErrorCode::UnexpectedToken.to_problem(vec![], position));
+        }
 
         // try to parse rainlang bindings if only there is at least one and current instance is
         // not an import itself, only owned rainlang bindings will be parsed at this point.
@@ -184,27 +174,43 @@ impl RainDocument {
         // specified entrypoints and they will be parsed only if they are part of the entrypoints or
         // their deps, see 'composer.rs'.
         if self.import_depth == 0 {
+            if remote_search {
+                self.search_pragma_metas().await;
+            }
             for binding in &mut self.bindings {
                 // parse the rainlang binding to ast and repopulate the
                 // binding.item and corresponding namespace with it
                 if matches!(binding.item, BindingItem::Exp(_)) {
-                    let rainlang_doc = RainlangDocument::create(
-                        binding.content.clone(),
-                        &self.namespace,
-                        self.known_words.as_ref(),
-                    );
+                    let rainlang_doc =
+                        if let Some(rainlang_doc) = reusable_bindings.get(&binding.name) {
+                            (*rainlang_doc).clone()
+                        } else {
+                            RainlangDocument::create(
+                                binding.content.clone(),
+                                &self.namespace,
+                                self.known_words.as_ref(),
+                                Some(&self.meta_store),
+                            )
+                        };
                     // add the rainlang problems to the binding problems by applying
-                    // the initial offset difference to their positions
-                    binding
-                        .problems
-                        .extend(rainlang_doc.problems.iter().map(|p| Problem {
-                            msg: p.msg.clone(),
-                            position: [
-                                p.position[0] + binding.content_position[0],
-                                p.position[1] + binding.content_position[0],
-                            ],
-                            code: p.code,
-                        }));
+                    // the initial offset difference to their positions, illegal characters
+                    // are already reported for the whole text
+                    binding.problems.extend(
+                        rainlang_doc
+                            .problems
+                            .iter()
+                            .filter(|p| p.code != ErrorCode::IllegalChar)
+                            .map(|p| Problem {
+                                msg: p.msg.clone(),
+                                position: [
+                                    p.position[0] + binding.content_position[0],
+                                    p.position[1] + binding.content_position[0],
+                                ],
+                                code: p.code,
+                                severity: p.severity,
+                                origin: p.origin.clone(),
+                            }),
+                    );
                     // assign to the binding.item and namespace
                     binding.item = BindingItem::Exp(rainlang_doc);
                     self.namespace.insert(
@@ -219,6 +225,11 @@ impl RainDocument {
             }
         }
 
+        self.check_word_specs();
+
+        // lints need the parsed rainlang bindings
+        self.apply_lints();
+
         Ok(())
     }
 
@@ -242,15 +253,95 @@ impl RainDocument {
         }
     }
 
+    /// Checks if the given imports resolve to the same namespace as the cached ones
+    pub(super) fn same_imports(imports: &[Import], cached_imports: &[Import]) -> bool {
+        let config = |imp: &Import| {
+            imp.configuration.as_ref().map(|v| {
+                v.groups
+                    .iter()
+                    .map(|(a, b)| (a.0.clone(), b.as_ref().map(|b| b.0.clone())))
+                    .collect::<Vec<_>>()
+            })
+        };
+        imports.len() == cached_imports.len()
+            && imports.iter().zip(cached_imports).all(|(imp, cached)| {
+                imp.name == cached.name
+                    && imp.hash == cached.hash
+                    && imp.problems.len() == cached.problems.len()
+                    && imp.sequence.is_some() == cached.sequence.is_some()
+                    && config(imp) == config(cached)
+            })
+    }
+
+    /// Finds the parsed rainlang of the cached bindings that can be reused for the given bindings,
+    /// a binding is reusable if its content is unchanged and it doesn't reference (directly or
+    /// through other bindings and quotes) any binding whose content has changed, added or removed
+    pub(super) fn reusable_bindings<'a>(
+        bindings: &[Binding],
+        cached_bindings: &'a [Binding],
+    ) -> HashMap<String, &'a RainlangDocument> {
+        let cached: HashMap<&str, &Binding> = cached_bindings
+            .iter()
+            .map(|v| (v.name.as_str(), v))
+            .collect();
+        let mut touched: Vec<&str> = bindings
+            .iter()
+            .filter(|v| {
+                cached
+                    .get(v.name.as_str())
+                    .is_none_or(|c| c.content != v.content)
+            })
+            .map(|v| v.name.as_str())
+            .collect();
+        touched.extend(
+            cached_bindings
+                .iter()
+                .filter(|c| !bindings.iter().any(|v| v.name == c.name))
+                .map(|c| c.name.as_str()),
+        );
+
+        // spread the changes to dependents until there are no more
+        let references = |binding: &Binding, names: &[&str]| {
+            binding
+                .content
+                .split(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_')
+                .any(|v| names.contains(&v))
+        };
+        let mut dirty = touched.clone();
+        loop {
+            let len = dirty.len();
+            for binding in bindings {
+                if !dirty.contains(&binding.name.as_str()) && references(binding, &dirty) {
+                    dirty.push(binding.name.as_str());
+                }
+            }
+            if dirty.len() == len {
+                break;
+            }
+        }
+
+        bindings
+            .iter()
+            .filter(|v| !dirty.contains(&v.name.as_str()))
+            .filter_map(|v| match &cached.get(v.name.as_str())?.item {
+                // words of pragmas depend on the meta store so they are resolved again
+                BindingItem::Exp(rainlang_doc) if rainlang_doc.pragmas.is_empty() => {
+                    Some((v.name.clone(), rainlang_doc))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Checks if a binding is elided and returns the elision msg if it found any
-    pub(super) fn is_elided(text: &str) -> Option<String> {
+    pub(crate) fn is_elided(text: &str) -> Option<String> {
         let msg = text.trim();
         msg.strip_prefix('!')
             .map(|stripped| stripped.trim().to_owned())
     }
 
     /// Checks if a text contains a single numeric value and returns it ie is constant binding
-    pub(super) fn is_literal(text: &str) -> Option<(String, usize, bool)> {
+    pub(crate) fn is_literal(text: &str) -> Option<(String, usize, bool)> {
         if text.starts_with('"') {
             let has_no_end = !STRING_LITERAL_PATTERN.is_match(text);
             Some((text.to_owned(), 0, has_no_end))
@@ -258,7 +349,7 @@ impl RainDocument {
             let has_no_end = !SUB_PARSER_LITERAL_PATTERN.is_match(text);
             Some((text.to_owned(), 1, has_no_end))
         } else {
-            let items = exclusive_parse(text, &WS_PATTERN, 0, false);
+            let items = split_whitespace(text, 0);
             if items.len() == 1 && NUMERIC_PATTERN.is_match(&items[0].0) {
                 Some((items[0].0.clone(), 2, false))
             } else {
@@ -267,8 +358,8 @@ impl RainDocument {
         }
     }
 
-    pub(super) fn is_quote(text: &str, offset: usize) -> Option<(String, Vec<ParsedItem>)> {
-        let items = exclusive_parse(text, &WS_PATTERN, offset, false);
+    pub(crate) fn is_quote(text: &str, offset: usize) -> Option<(String, Vec<ParsedItem>)> {
+        let items = split_whitespace(text, offset);
         let first = items.first()?;
         if QUOTE_PATTERN.is_match(&first.0) {
             Some((first.0[1..].to_owned(), items[1..].to_vec()))
@@ -291,15 +382,15 @@ impl RainDocument {
                 let mut temp: ParsedItem;
                 // to build the complementary piece if it is a string literal with whitespaces
                 if complementary_piece.0.starts_with('"')
-                    && (complementary_piece.0 == "\"" || !complementary_piece.0.ends_with('"'))
+                    && string_literal_end(&complementary_piece.0).is_none()
                 {
                     let start = complementary_piece.1[0];
-                    let mut end = 0;
+                    let mut end = complementary_piece.1[1];
                     let mut has_no_end = true;
                     #[allow(clippy::while_let_on_iterator)]
                     while let Some(end_item) = config_pieces.next() {
                         end = end_item.1[1];
-                        if end_item.0.ends_with('"') {
+                        if string_literal_end(&original_text[start..end]).is_some() {
                             has_no_end = false;
                             break;
                         }
@@ -317,7 +408,7 @@ impl RainDocument {
                     && (complementary_piece.0 == "]" || !complementary_piece.0.ends_with(']'))
                 {
                     let start = complementary_piece.1[0];
-                    let mut end = 0;
+                    let mut end = complementary_piece.1[1];
                     let mut has_no_end = true;
                     #[allow(clippy::while_let_on_iterator)]
                     while let Some(end_item) = config_pieces.next() {
@@ -427,6 +518,7 @@ impl RainDocument {
         &self,
         statement: &ParsedItem,
         remote_search: bool,
+        cached_imports: &[Import],
     ) -> Import {
         let at_pos: Offsets = [statement.1[0] - 1, statement.1[0] - 1];
         let mut result = Import {
@@ -442,7 +534,7 @@ impl RainDocument {
 
         // parse all items delimited by whitespaces
         let mut is_valid = false;
-        let mut pieces = exclusive_parse(&statement.0, &WS_PATTERN, statement.1[0], false);
+        let mut pieces = split_whitespace(&statement.0, statement.1[0]);
         if let Some(name_or_hash) = pieces.first() {
             let mut config_pieces_start_index = 1;
             if HEX_PATTERN.is_match(&name_or_hash.0) {
@@ -510,6 +602,25 @@ impl RainDocument {
             return result;
         }
 
+        // reuse the resolved contents of the same hash from the previous parse
+        if let Some(cached) = cached_imports
+            .iter()
+            .find(|v| v.hash == result.hash && v.sequence.is_some())
+        {
+            result.sequence = cached.sequence.clone();
+            result.problems.extend(
+                cached
+                    .problems
+                    .iter()
+                    .filter(|v| v.code == ErrorCode::InvalidRainDocument)
+                    .map(|v| Problem {
+                        position: result.hash_position,
+                        ..v.clone()
+                    }),
+            );
+            return result;
+        }
+
         let hash_bytes = alloy_primitives::hex::decode(&result.hash).unwrap();
         let subgraphs = { self.meta_store.read().unwrap().subgraphs().clone() };
 
@@ -534,6 +645,120 @@ impl RainDocument {
         result
     }
 
+    /// Checks the opcodes of the rainlang bindings against this instance's word specs and
+    /// replaces their previous word spec problems, imported dotrains are not checked
+    pub(crate) fn check_word_specs(&mut self) {
+        if self.import_depth != 0 {
+            return;
+        }
+        for binding in &mut self.bindings {
+            if let BindingItem::Exp(rainlang_doc) = &mut binding.item {
+                rainlang_doc.check_word_specs(&self.word_specs);
+                binding
+                    .problems
+                    .retain(|v| !WORD_SPEC_CODES.contains(&v.code));
+                binding.problems.extend(
+                    rainlang_doc
+                        .problems
+                        .iter()
+                        .filter(|p| WORD_SPEC_CODES.contains(&p.code))
+                        .map(|p| Problem {
+                            msg: p.msg.clone(),
+                            position: [
+                                p.position[0] + binding.content_position[0],
+                                p.position[1] + binding.content_position[0],
+                            ],
+                            code: p.code,
+                            severity: p.severity,
+                            origin: p.origin.clone(),
+                        }),
+                );
+                self.namespace.insert(
+                    binding.name.clone(),
+                    NamespaceItem::Leaf(NamespaceLeaf {
+                        hash: String::new(),
+                        import_index: -1,
+                        element: binding.clone(),
+                    }),
+                );
+            }
+        }
+    }
+
+    /// the pragma hashes of the rainlang bindings, read from their tokens with the same pragma
+    /// keyword scan as rainlang parsing without parsing the bindings
+    pub(super) fn pragma_hashes(&self) -> Vec<String> {
+        let mut hashes: Vec<String> = vec![];
+        for binding in &self.bindings {
+            if !matches!(binding.item, BindingItem::Exp(_)) {
+                continue;
+            }
+            // the sources of a pragma are the words up to the next pragma keyword, the last
+            // one ends at its first word that is neither a literal nor a binding reference
+            let text = &binding.content;
+            let tokens = tokenize(text, 0);
+            let keywords = pragma_keywords(text, &tokens);
+            for (i, keyword) in keywords.iter().enumerate() {
+                let end = keywords.get(i + 1).map_or(text.len(), |v| v[0]);
+                for token in tokens
+                    .iter()
+                    .filter(|v| v.position[0] >= keyword[1] && v.position[1] <= end)
+                {
+                    if matches!(token.kind, TokenKind::Whitespace | TokenKind::Comment) {
+                        continue;
+                    }
+                    let word = &text[token.position[0]..token.position[1]];
+                    let hash = if token.kind != TokenKind::Word {
+                        break;
+                    } else if LITERAL_PATTERN.is_match(word) {
+                        word
+                    } else if let Some(binding) = search_binding_ref(word, &self.namespace) {
+                        match &binding.item {
+                            BindingItem::Literal(literal) => &literal.value,
+                            _ => continue,
+                        }
+                    } else if i == keywords.len() - 1 {
+                        break;
+                    } else {
+                        continue;
+                    };
+                    if HASH_PATTERN.is_match(hash) && !hashes.iter().any(|v| v == hash) {
+                        hashes.push(hash.to_owned());
+                    }
+                }
+            }
+        }
+        hashes
+    }
+
+    /// searches the subgraphs for the deployers or metas of the pragma hashes of the rainlang
+    /// bindings that are not in the meta store yet, so their words get known when parsing
+    pub(super) async fn search_pragma_metas(&self) {
+        let subgraphs = { self.meta_store.read().unwrap().subgraphs().clone() };
+        for hash in self.pragma_hashes() {
+            let hash_bytes = alloy_primitives::hex::decode(&hash).unwrap();
+            let is_cached = {
+                let meta_store = self.meta_store.read().unwrap();
+                meta_store.get_deployer(&hash_bytes).is_some()
+                    || meta_store.get_meta(&hash_bytes).is_some()
+            };
+            if is_cached {
+                continue;
+            }
+            if let Ok(deployer) = search_deployer(&hash, &subgraphs).await {
+                self.meta_store
+                    .write()
+                    .unwrap()
+                    .set_deployer_from_query_response(deployer);
+            } else if let Ok(meta_res) = search(&hash, &subgraphs).await {
+                self.meta_store
+                    .write()
+                    .unwrap()
+                    .update_with(&hash_bytes, &meta_res.bytes);
+            }
+        }
+    }
+
     // read the corresponding hash from CAS, the result is either a deployer or a meta or not found
     // this should be done with care for the CAS read/write lock
     pub(super) async fn fetch_import_contents(
@@ -620,11 +845,18 @@ impl RainDocument {
                             } else {
                                 dotrain.parse(false, None).await;
                             }
-                            if !dotrain.problems.is_empty() {
-                                result.problems.push(
-                                    ErrorCode::InvalidRainDocument
-                                        .to_problem(vec![], result.hash_position),
-                                );
+                            if let Some(problem) = dotrain.problems.first() {
+                                // point to the first problem of the imported dotrain
+                                let mut origin = vec![ProblemOrigin {
+                                    hash: result.hash.clone(),
+                                    uri: self.import_uri(&result.hash),
+                                    position: problem.position,
+                                }];
+                                origin.extend(problem.origin.clone().unwrap_or_default());
+                                let mut invalid_problem = ErrorCode::InvalidRainDocument
+                                    .to_problem(vec![], result.hash_position);
+                                invalid_problem.origin = Some(origin);
+                                result.problems.push(invalid_problem);
                             }
                             result.sequence.as_mut().unwrap().dotrain = Some(dotrain);
                         } else {
@@ -735,6 +967,7 @@ impl RainDocument {
                                 } else {
                                     leaf.element.item = BindingItem::Literal(LiteralBindingItem {
                                         value: new_conf.0.clone(),
+                                        parsed: LiteralValue::parse(&new_conf.0).ok(),
                                     })
                                 }
                             } else {
@@ -767,8 +1000,14 @@ impl RainDocument {
         let content_position: Offsets;
         let mut raw_content = ""; // without comments
 
-        if let Some(boundry_offset) = parsed_binding.0.find([' ', '\t', '\r', '\n']) {
-            let slices = parsed_binding.0.split_at(boundry_offset + 1);
+        // name spans up to the first whitespace, same as the binding's name token of the CST
+        if let Some((boundry_offset, boundry)) = parsed_binding
+            .0
+            .char_indices()
+            .find(|(_, c)| c.is_whitespace())
+        {
+            let boundry_end = boundry_offset + boundry.len_utf8();
+            let slices = parsed_binding.0.split_at(boundry_end);
             let raw_trimmed = tracked_trim(slices.1);
             raw_content = if raw_trimmed.0.is_empty() {
                 slices.1
@@ -781,19 +1020,16 @@ impl RainDocument {
                 .get(parsed_binding.1[0]..parsed_binding.1[1])
                 .unwrap()
                 .to_owned();
-            name = slices.0[..slices.0.len() - 1].to_owned();
+            name = slices.0[..boundry_offset].to_owned();
             name_position = [parsed_binding.1[0], parsed_binding.1[0] + boundry_offset];
 
-            let slices = content_text.split_at(boundry_offset + 1);
+            let slices = content_text.split_at(boundry_end);
             let trimmed_content = tracked_trim(slices.1);
             content_position = if trimmed_content.0.is_empty() {
-                [
-                    parsed_binding.1[0] + boundry_offset + 1,
-                    parsed_binding.1[1],
-                ]
+                [parsed_binding.1[0] + boundry_end, parsed_binding.1[1]]
             } else {
                 [
-                    parsed_binding.1[0] + boundry_offset + 1 + trimmed_content.1,
+                    parsed_binding.1[0] + boundry_end + trimmed_content.1,
                     parsed_binding.1[1] - trimmed_content.2,
                 ]
             };
@@ -831,28 +1067,16 @@ impl RainDocument {
                     msg = DEFAULT_ELISION.to_owned();
                 }
                 item = BindingItem::Elided(ElidedBindingItem { msg });
-            } else if let Some((value, typ, has_err)) = Self::is_literal(raw_content) {
-                if typ == 0 {
-                    if has_err {
-                        self.problems.push(
-                            ErrorCode::UnexpectedStringLiteralEnd
-                                .to_problem(vec![], content_position),
-                        );
+            } else if let Some((value, _, _)) = Self::is_literal(raw_content) {
+                let parsed = match LiteralValue::parse(&value) {
+                    Ok(v) => Some(v),
+                    Err(code) => {
+                        self.problems
+                            .push(code.to_problem(vec![], content_position));
+                        None
                     }
-                } else if typ == 1 {
-                    if has_err {
-                        self.problems.push(
-                            ErrorCode::UnexpectedSubParserEnd.to_problem(vec![], content_position),
-                        );
-                    }
-                } else if HEX_PATTERN.is_match(&value) && value.len() % 2 == 1 {
-                    self.problems
-                        .push(ErrorCode::OddLenHex.to_problem(vec![], content_position));
-                } else if has_err {
-                    self.problems
-                        .push(ErrorCode::OutOfRangeValue.to_problem(vec![], content_position));
-                }
-                item = BindingItem::Literal(LiteralBindingItem { value });
+                };
+                item = BindingItem::Literal(LiteralBindingItem { value, parsed });
             } else if let Some((quote, rest)) = Self::is_quote(raw_content, content_position[0]) {
                 for unexpected_token in rest {
                     self.problems
@@ -1005,15 +1229,13 @@ impl RainDocument {
             let value = raw_value.trim();
             if NAMESPACE_PATTERN.is_match(&key) {
                 let item;
-                if let Some((literal_value, _, has_err)) = Self::is_literal(value) {
-                    if has_err {
-                        return Err(Error::InvalidOverride(format!(
-                            "invalid rebind value: {}",
-                            value
-                        )));
-                    }
+                if let Some((literal_value, _, _)) = Self::is_literal(value) {
+                    let parsed = LiteralValue::parse(&literal_value).map_err(|_| {
+                        Error::InvalidOverride(format!("invalid rebind value: {}", value))
+                    })?;
                     item = BindingItem::Literal(LiteralBindingItem {
                         value: literal_value,
+                        parsed: Some(parsed),
                     });
                 } else if let Some((quote, rest)) = Self::is_quote(value, 0) {
                     if !rest.is_empty() {
@@ -1247,6 +1469,9 @@ impl PartialEq for RainDocument {
             && self.namespace == other.namespace
             && self.imports == other.imports
             && self.known_words == other.known_words
+            && self.front_matter_schema == other.front_matter_schema
+            && self.lint_config == other.lint_config
+            && self.word_specs == other.word_specs
             && self.problems == other.problems
             && self.error == other.error
     }
@@ -7,6 +7,8 @@ use super::super::{
     error::{Error, ErrorCode},
     types::{ast::*, patterns::*},
 };
+use super::frontmatter::FrontMatterSchema;
+use super::super::{linter::LintConfig, types::words::WordSpecs};
 
 #[cfg(feature = "js-api")]
 use tsify::Tsify;
@@ -18,6 +20,24 @@ use wasm_bindgen::prelude::wasm_bindgen;
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct Rebind(pub String, pub String);
 
+/// Type of an edit on a RainDocument text, range is the byte offsets of the replaced text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct TextEdit {
+    pub range: Offsets,
+    pub new_text: String,
+}
+
+/// Items of a previous parse of a RainDocument that can be reused by the next parse
+#[derive(Debug, Default)]
+pub(crate) struct ParseCache {
+    pub(crate) imports: Vec<Import>,
+    pub(crate) bindings: Vec<Binding>,
+    pub(crate) known_words: Option<AuthoringMeta>,
+    pub(crate) rebinds: Option<Vec<Rebind>>,
+}
+
 mod logic;
 
 /// Data structure of a parsed .rain text
@@ -55,7 +75,7 @@ let entrypoints = vec![
 ];
 
 // compose this instance to get rainlang string
-let result = rain_document.compose(&entrypoints);
+let result = rain_document.compose(&entrypoints, None);
 ```
 "#
 )]
@@ -96,6 +116,18 @@ pub struct RainDocument {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "js-api", tsify(type = "IAuthoringMeta"))]
     pub(crate) known_words: Option<AuthoringMeta>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub(crate) front_matter_schema: Option<FrontMatterSchema>,
+    #[serde(default, skip_serializing_if = "LintConfig::is_empty")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub(crate) lint_config: LintConfig,
+    #[serde(default, skip_serializing_if = "WordSpecs::is_empty")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub(crate) word_specs: WordSpecs,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub(crate) rebinds: Option<Vec<Rebind>>,
 }
 
 impl RainDocument {
@@ -147,6 +179,38 @@ impl RainDocument {
         self.parse(true, rebinds).await;
     }
 
+    /// Applies the given edits to the text in order (each edit's range is based on the text
+    /// resulted from the edits before it) and reparses right away with remote meta search disabled,
+    /// unlike [RainDocument::update] the resolved imports are reused for unchanged hashes and only
+    /// the rainlang bindings that were touched by the edits or depend on a touched binding get
+    /// parsed again, errors without applying any edit if an edit range is out of text bounds
+    pub fn apply_edits(
+        &mut self,
+        edits: Vec<TextEdit>,
+        rebinds: Option<Vec<Rebind>>,
+    ) -> Result<(), Error> {
+        let mut text = self.text.clone();
+        for edit in &edits {
+            if edit.range[0] > edit.range[1]
+                || edit.range[1] > text.len()
+                || !text.is_char_boundary(edit.range[0])
+                || !text.is_char_boundary(edit.range[1])
+            {
+                return Err(Error::OutOfCharBoundry);
+            }
+            text.replace_range(edit.range[0]..edit.range[1], &edit.new_text);
+        }
+        self.text = text;
+        let cache = ParseCache {
+            imports: std::mem::take(&mut self.imports),
+            bindings: std::mem::take(&mut self.bindings),
+            known_words: self.known_words.clone(),
+            rebinds: self.rebinds.take(),
+        };
+        block_on(self.parse_with_cache(false, rebinds, Some(cache)));
+        Ok(())
+    }
+
     /// This instance's current text
     pub fn text(&self) -> &str {
         &self.text
@@ -205,6 +269,18 @@ impl RainDocument {
         &self.known_words
     }
 
+    /// This instance's word specs
+    pub fn word_specs(&self) -> &WordSpecs {
+        &self.word_specs
+    }
+
+    /// Sets the specs of words that the opcodes of the rainlang bindings are checked against
+    /// and rechecks them right away without reparsing
+    pub fn set_word_specs(&mut self, word_specs: WordSpecs) {
+        self.word_specs = word_specs;
+        self.check_word_specs();
+    }
+
     /// The error msg if parsing had resulted in an error
     pub fn runtime_error(&self) -> &Option<String> {
         &self.error
@@ -223,12 +299,66 @@ impl RainDocument {
         self.bindings.iter().flat_map(|v| &v.problems).collect()
     }
 
+    /// Searches through this instance's imports recursively for the imported dotrain of the given hash
+    pub fn import_document(&self, hash: &str) -> Option<&RainDocument> {
+        for import in &self.imports {
+            if let Some(dotrain) = import.sequence.as_ref().and_then(|v| v.dotrain.as_ref()) {
+                if import.hash == hash {
+                    return Some(dotrain);
+                } else if let Some(nested) = dotrain.import_document(hash) {
+                    return Some(nested);
+                }
+            }
+        }
+        None
+    }
+
+    /// The URI of the dotrain of the given hash if it is known to this instance's meta Store
+    pub fn import_uri(&self, hash: &str) -> Option<String> {
+        let hash_bytes = alloy_primitives::hex::decode(hash).ok()?;
+        self.meta_store
+            .read()
+            .unwrap()
+            .get_dotrain_uri(&hash_bytes)
+            .cloned()
+    }
+
+    /// Returns a copy of this instance with the given rebinds applied to its namespace without
+    /// reparsing the text, so imports are not resolved again, invalid rebinds are reported as
+    /// a problem of the returned instance same as they are when parsing with rebinds
+    pub fn with_rebinds(&self, rebinds: Vec<Rebind>) -> RainDocument {
+        let mut rain_document = self.clone();
+        if let Err(e) = Self::apply_overrides(rebinds, &mut rain_document.namespace) {
+            if let Error::InvalidOverride(err_msg) = e {
+                rain_document
+                    .problems
+                    .push(ErrorCode::InvalidSuppliedRebindings.to_problem(vec![&err_msg], [0, 0]));
+            } else {
+                rain_document.error = Some(e.to_string());
+                rain_document
+                    .problems
+                    .push(ErrorCode::RuntimeError.to_problem(vec![&e.to_string()], [0, 0]));
+            }
+        }
+        rain_document
+    }
+
     /// Parses this instance's text
     #[cfg_attr(target_family = "wasm", async_recursion(?Send))]
     #[cfg_attr(not(target_family = "wasm"), async_recursion)]
     pub async fn parse(&mut self, enable_remote: bool, rebinds: Option<Vec<Rebind>>) {
+        self.parse_with_cache(enable_remote, rebinds, None).await
+    }
+
+    /// Parses this instance's text reusing the items of the given previous parse
+    pub(crate) async fn parse_with_cache(
+        &mut self,
+        enable_remote: bool,
+        rebinds: Option<Vec<Rebind>>,
+        cache: Option<ParseCache>,
+    ) {
         if NON_EMPTY_PATTERN.is_match(&self.text) {
-            if let Err(e) = self._parse(enable_remote, rebinds).await {
+            if let Err(e) = self._parse(enable_remote, rebinds, cache).await {
                 if let Error::InvalidOverride(err_msg) = e {
                     self.problems.push(
                         ErrorCode::InvalidSuppliedRebindings.to_problem(vec![&err_msg], [0, 0]),
@@ -246,7 +376,6 @@ impl RainDocument {
             self.comments.clear();
             self.bindings.clear();
             self.namespace.clear();
-            self.known_words = None;
             self.front_matter_offset = 0;
         }
     }
@@ -271,6 +400,10 @@ impl RainDocument {
             comments: vec![],
             problems: vec![],
             import_depth,
+            front_matter_schema: None,
+            lint_config: LintConfig::default(),
+            word_specs: WordSpecs::default(),
+            rebinds: None,
         }
     }
 }
@@ -280,7 +413,11 @@ mod tests {
     use super::*;
     use crate::exclusive_parse;
     use std::collections::HashMap;
-    use super::super::rainlangdocument::RainlangDocument;
+    use super::super::{illegal_code_chars, rainlangdocument::RainlangDocument};
+    use rain_metadata::{types::authoring::v1::AuthoringMetaItem, NPE2Deployer};
+    use alloy_primitives::U256;
+    use crate::types::{literal::LiteralValue, words::WordSpec};
+    use proptest::prelude::*;
 
     #[test]
     fn test_is_constant_method() {
@@ -449,12 +586,12 @@ mod tests {
             0,
             None,
         );
-        let statements = vec![ParsedItem(
+        let statements = [ParsedItem(
             "0x6518ec1930d8846b093dcff41a6ee6f6352c72b82e48584cce741a9e8a6d6184".to_owned(),
             [17, 83],
         )];
 
-        let result = block_on(rain_document.process_import(&statements[0], false));
+        let result = block_on(rain_document.process_import(&statements[0], false, &[]));
         let expected = Import {
             name: ".".to_owned(),
             name_position: [17, 83],
@@ -486,6 +623,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -504,6 +642,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -529,6 +668,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -547,6 +687,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -575,6 +716,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -593,6 +735,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -624,6 +767,7 @@ mod tests {
                     problems: vec![],
                     item: BindingItem::Literal(LiteralBindingItem {
                         value: "3e18".to_owned(),
+                        parsed: LiteralValue::parse("3e18").ok(),
                     }),
                 },
             }),
@@ -673,6 +817,7 @@ _: opcode-1(0xabcd 456);
                 problems: vec![],
                 item: BindingItem::Literal(LiteralBindingItem {
                     value: "4e18".to_owned(),
+                    parsed: LiteralValue::parse("4e18").ok(),
                 }),
             },
             Binding {
@@ -697,6 +842,7 @@ _: opcode-1(0xabcd 456);
                     "_: opcode-1(0xabcd 456);".to_owned(),
                     &HashMap::new(),
                     None,
+                    None,
                 )),
             },
         ];
@@ -741,6 +887,10 @@ _: opcode-1(0xabcd 456);
             namespace: expected_namespace,
             meta_store: meta_store.clone(),
             known_words: None,
+            front_matter_schema: None,
+            lint_config: LintConfig::default(),
+            word_specs: WordSpecs::default(),
+            rebinds: None,
         };
         assert_eq!(rain_document, expected_rain_document);
 
@@ -760,6 +910,7 @@ _: opcode-1(0xabcd 456);
             problems: vec![],
             item: BindingItem::Literal(LiteralBindingItem {
                 value: "4e18".to_string(),
+                parsed: LiteralValue::parse("4e18").ok(),
             }),
         }];
         let mut expected_namespace: Namespace = HashMap::new();
@@ -784,7 +935,492 @@ _: opcode-1(0xabcd 456);
             namespace: expected_namespace,
             meta_store,
             known_words: None,
+            front_matter_schema: None,
+            lint_config: LintConfig::default(),
+            word_specs: WordSpecs::default(),
+            rebinds: None,
         };
         assert_eq!(rain_document, expected_rain_document);
     }
+
+    #[test]
+    fn test_parse_unicode() {
+        let text = r#"nom: "données ✓"
+---
+/* commentaire en français ✓ */
+#greeting "héllo wörld"
+#exp
+/* 日本語 */
+_: opcode-1("ü ü" greeting);
+"#;
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        assert!(rain_document.all_problems().is_empty());
+        assert_eq!(rain_document.front_matter_offset, 20);
+        assert_eq!(
+            rain_document.comments,
+            vec![
+                Comment {
+                    comment: "/* commentaire en français ✓ */".to_owned(),
+                    position: [24, 58],
+                },
+                Comment {
+                    comment: "/* 日本語 */".to_owned(),
+                    position: [90, 105],
+                },
+            ]
+        );
+        let greeting = &rain_document.bindings[0];
+        assert_eq!(greeting.content, "\"héllo wörld\"");
+        assert_eq!(greeting.content_position, [69, 84]);
+        assert_eq!(&text[69..84], greeting.content);
+        let BindingItem::Exp(exp) = &rain_document.bindings[1].item else {
+            panic!("expected rainlang binding");
+        };
+        let Node::Opcode(opcode) = &exp.ast[0].lines[0].nodes[0] else {
+            panic!("expected opcode");
+        };
+        let Node::Literal(literal) = &opcode.inputs[0] else {
+            panic!("expected literal");
+        };
+        assert_eq!(literal.value, "\"ü ü\"");
+        let offset = rain_document.bindings[1].content_position[0];
+        assert_eq!(
+            &text[literal.position[0] + offset..literal.position[1] + offset],
+            "\"ü ü\""
+        );
+
+        // non ASCII chars are still illegal out of comments and string literals
+        let text = "---\n/* ✓ */\n#exp\n_: opcode-✓(1);\n";
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        assert_eq!(
+            rain_document.problems,
+            vec![ErrorCode::IllegalChar.to_problem(vec!["✓"], [29, 32])]
+        );
+    }
+
+    #[test]
+    fn test_parse_illegal_chars() {
+        let text = "---\n#lit∂ 1\n#exp\n_: add(1 ∂2) mul(3 4);\n#other 4e18\n";
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+
+        // all illegal chars are reported and the rest of the text is still parsed
+        assert_eq!(
+            rain_document.all_problems(),
+            vec![
+                &ErrorCode::IllegalChar.to_problem(vec!["∂"], [8, 11]),
+                &ErrorCode::IllegalChar.to_problem(vec!["∂"], [28, 31]),
+                &ErrorCode::InvalidWordPattern.to_problem(vec!["lit∂"], [5, 11]),
+                &ErrorCode::InvalidWordPattern.to_problem(vec!["∂2"], [28, 32]),
+            ]
+        );
+        assert_eq!(
+            rain_document
+                .bindings
+                .iter()
+                .map(|v| v.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["exp", "other"]
+        );
+        let BindingItem::Exp(exp) = &rain_document.bindings[0].item else {
+            panic!("expected rainlang binding");
+        };
+        assert_eq!(exp.ast[0].lines[0].nodes.len(), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_arbitrary_unicode(
+            text in "[ab0x1/*@#;,:()<>\"\\[\\]!'. \n-∂é𝄞\u{a0}]{0,80}"
+        ) {
+            let text = format!("---\n{text}");
+            let rain_document = RainDocument::create(text.clone(), None, None, None);
+            prop_assert_eq!(
+                rain_document
+                    .problems
+                    .iter()
+                    .filter(|v| v.code == ErrorCode::IllegalChar)
+                    .count(),
+                illegal_code_chars(&text, 4).len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_pragma_words() {
+        let word = |word: &str, description: &str| AuthoringMetaItem {
+            word: word.to_owned(),
+            operand_parser_offset: 0,
+            description: description.to_owned(),
+        };
+        let hash = [1u8; 32];
+        let mut store = Store::new();
+        store.set_deployer(
+            &hash,
+            &NPE2Deployer {
+                authoring_meta: Some(AuthoringMeta(vec![word("add", "adds numbers")])),
+                ..Default::default()
+            },
+            None,
+        );
+        let meta_store = Arc::new(RwLock::new(store));
+
+        // words of the pragma are merged with the known words
+        let text = format!(
+            r"---
+#main
+using-words-from {}
+_: add(1 2),
+_: sub(1 2),
+_: mul(1 2);
+",
+            alloy_primitives::hex::encode_prefixed(hash)
+        );
+        let words = AuthoringMeta(vec![word("sub", "subtracts numbers")]);
+        let mut rain_document =
+            RainDocument::create(text.clone(), Some(meta_store.clone()), Some(words), None);
+        let BindingItem::Exp(rainlang_doc) = &rain_document.bindings()[0].item else {
+            panic!("expected rainlang binding")
+        };
+        let descriptions = rainlang_doc.ast[0].lines[0]
+            .nodes
+            .iter()
+            .chain(&rainlang_doc.ast[0].lines[1].nodes)
+            .filter_map(|v| match v {
+                Node::Opcode(op) => Some(op.opcode.description.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(descriptions, vec!["adds numbers", "subtracts numbers"]);
+        let position = text.find("mul").unwrap();
+        let expected_problems =
+            vec![ErrorCode::UndefinedWord.to_problem(vec!["mul"], [position, position + 3])];
+        assert_eq!(rain_document.bindings()[0].problems, expected_problems);
+
+        // known words are kept on updates
+        rain_document.update(text.clone(), None);
+        assert_eq!(rain_document.bindings()[0].problems, expected_problems);
+
+        // words cannot be fully known if a pragma hash is not found, so no undefined words
+        let text = text.replace(&"01".repeat(32), &"02".repeat(32));
+        let rain_document = RainDocument::create(text, Some(meta_store), None, None);
+        assert!(rain_document.bindings()[0].problems.is_empty());
+
+        // pragma hashes are read from pragma statements only, not from comments, strings or
+        // the expression after the pragma sources
+        let hash = |v: &str| format!("0x{}", v.repeat(32));
+        let text = format!(
+            "---\n#h {}\n#main\n/* using-words-from {} */\nusing-words-from {} h\n_ _: \"using-words-from {}\" {};",
+            hash("03"),
+            hash("04"),
+            hash("01"),
+            hash("05"),
+            hash("06"),
+        );
+        let rain_document = RainDocument::create(text, None, None, None);
+        assert_eq!(rain_document.pragma_hashes(), vec![hash("01"), hash("03")]);
+
+        // rainlang bindings of a previous parse are not reused if the words have changed since
+        let text = "---\n#main\n_: mul(1 2);\n";
+        let mut rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        assert!(rain_document.bindings()[0].problems.is_empty());
+        let cache = ParseCache {
+            imports: std::mem::take(&mut rain_document.imports),
+            bindings: std::mem::take(&mut rain_document.bindings),
+            known_words: rain_document.known_words.take(),
+            rebinds: None,
+        };
+        rain_document.known_words = Some(AuthoringMeta(vec![word("add", "adds numbers")]));
+        block_on(rain_document.parse_with_cache(false, None, Some(cache)));
+        let position = text.find("mul").unwrap();
+        assert_eq!(
+            rain_document.bindings()[0].problems,
+            vec![ErrorCode::UndefinedWord.to_problem(vec!["mul"], [position, position + 3])]
+        );
+    }
+
+    #[test]
+    fn test_set_word_specs_method() {
+        let text = r"---
+#main
+_: add(1 2 3);
+";
+        let mut rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        assert!(rain_document.all_problems().is_empty());
+        assert!(rain_document.compose(&["main"], None).is_ok());
+
+        // arity problems are added to the binding and make the document uncomposable
+        rain_document.set_word_specs(WordSpecs(HashMap::from([(
+            "add".to_owned(),
+            WordSpec {
+                inputs: 2,
+                max_inputs: None,
+                outputs: 1,
+                operand_args: None,
+            },
+        )])));
+        let position = text.find("add").unwrap();
+        let expected_problems =
+            vec![ErrorCode::OutOfRangeInputs
+                .to_problem(vec!["2", "add", "3"], [position, position + 3])];
+        assert_eq!(rain_document.bindings()[0].problems, expected_problems);
+        let NamespaceItem::Leaf(leaf) = &rain_document.namespace()["main"] else {
+            panic!("expected namespace leaf")
+        };
+        assert_eq!(leaf.element.problems, expected_problems);
+        assert!(rain_document.compose(&["main"], None).is_err());
+
+        // arity is checked on reparse as well
+        rain_document.update(text.to_owned(), None);
+        assert_eq!(rain_document.bindings()[0].problems, expected_problems);
+        rain_document.update(text.replace(" 3)", ")"), None);
+        assert!(rain_document.all_problems().is_empty());
+        rain_document.set_word_specs(WordSpecs::default());
+        assert!(rain_document.all_problems().is_empty());
+    }
+
+    #[test]
+    fn test_literal_values() {
+        let imprecise = "0.".to_owned() + &"9".repeat(68);
+        let text = format!(
+            r"---
+#value 1.5e2
+#overflow 1e2147483647
+#imprecise {}
+#main
+_: add(value 0x0102 1e2147483647 1.2345e-14 150 {});
+",
+            imprecise,
+            U256::MAX
+        );
+        let rain_document = RainDocument::create(text.clone(), None, None, None);
+        let decimal = |coefficient: u64, exponent: i32| LiteralValue::Decimal {
+            coefficient: U256::from(coefficient),
+            exponent,
+        };
+        let BindingItem::Literal(value) = &rain_document.bindings()[0].item else {
+            panic!("expected literal binding")
+        };
+        assert_eq!(value.parsed, Some(decimal(15, 1)));
+        let BindingItem::Literal(overflow) = &rain_document.bindings()[1].item else {
+            panic!("expected literal binding")
+        };
+        assert_eq!(overflow.parsed, None);
+
+        let overflow_position = text.find("1e2147483647").unwrap();
+        let imprecise_position = text.find(&imprecise).unwrap();
+        let rhs_overflow_position = text.rfind("1e2147483647").unwrap();
+        let expected_problems = [
+            ErrorCode::OutOfRangeValue
+                .to_problem(vec![], [overflow_position, overflow_position + 12]),
+            ErrorCode::OutOfRangePrecision
+                .to_problem(vec![], [imprecise_position, imprecise_position + 70]),
+            ErrorCode::OutOfRangeValue
+                .to_problem(vec![], [rhs_overflow_position, rhs_overflow_position + 12]),
+        ];
+        assert_eq!(
+            rain_document.all_problems(),
+            expected_problems.iter().collect::<Vec<_>>()
+        );
+
+        // literal references carry the typed value of the referenced binding
+        let BindingItem::Exp(rainlang_doc) = &rain_document.bindings()[3].item else {
+            panic!("expected expression binding")
+        };
+        let Node::Opcode(op) = &rainlang_doc.ast()[0].lines[0].nodes[0] else {
+            panic!("expected opcode")
+        };
+        let parsed = op
+            .inputs
+            .iter()
+            .map(|v| match v {
+                Node::Literal(v) => v.parsed.clone(),
+                _ => panic!("expected literal"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parsed,
+            vec![
+                value.parsed.clone(),
+                Some(LiteralValue::Hex(vec![1, 2].into())),
+                None,
+                Some(decimal(12345, -18)),
+                Some(LiteralValue::Integer(U256::from(150))),
+                Some(LiteralValue::Integer(U256::MAX)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_literals() {
+        let text = r#"---
+#quoted "say \"hi\" \x41"
+#long "this string is longer than 31 bytes"
+#main
+_: concat(quoted "a \"b c\"\n" "\q" "0123456789012345678901234567890\\");
+"#;
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        let BindingItem::Literal(quoted) = &rain_document.bindings()[0].item else {
+            panic!("expected literal binding")
+        };
+        assert_eq!(
+            quoted.parsed,
+            Some(LiteralValue::String("say \"hi\" A".to_owned()))
+        );
+
+        let long_position = text.find("\"this").unwrap();
+        let escape_position = text.find("\"\\q\"").unwrap();
+        let rhs_long_position = text.find("\"0123").unwrap();
+        let expected_problems = [
+            ErrorCode::OutOfRangeStringLength
+                .to_problem(vec![], [long_position, long_position + 37]),
+            ErrorCode::InvalidEscapeSequence
+                .to_problem(vec![], [escape_position, escape_position + 4]),
+            ErrorCode::OutOfRangeStringLength
+                .to_problem(vec![], [rhs_long_position, rhs_long_position + 35]),
+        ];
+        assert_eq!(
+            rain_document.all_problems(),
+            expected_problems.iter().collect::<Vec<_>>()
+        );
+
+        // escaped quotes do not end the string literal on the RHS
+        let BindingItem::Exp(rainlang_doc) = &rain_document.bindings()[2].item else {
+            panic!("expected expression binding")
+        };
+        let Node::Opcode(op) = &rainlang_doc.ast()[0].lines[0].nodes[0] else {
+            panic!("expected opcode")
+        };
+        let Node::Literal(literal) = &op.inputs[1] else {
+            panic!("expected literal")
+        };
+        assert_eq!(literal.value, r#""a \"b c\"\n""#);
+        assert_eq!(
+            literal.parsed,
+            Some(LiteralValue::String("a \"b c\"\n".to_owned()))
+        );
+        assert_eq!(op.inputs.len(), 4);
+    }
+
+    #[test]
+    fn test_apply_edits_method() {
+        let mut store = Store::new();
+        let import_text = r"---
+#exp
+_: opcode-2(1 2);
+";
+        let (import_hash, _) = store
+            .set_dotrain(import_text, "file:///import.rain", false)
+            .unwrap();
+        let import_hash = alloy_primitives::hex::encode_prefixed(import_hash);
+        let meta_store = Arc::new(RwLock::new(store));
+
+        let text = format!(
+            r"---
+@imp {}
+#value 1
+#quoted 'main
+#main
+_: opcode-1(value 2);
+#other
+_: opcode-3<'quoted>(3 4);
+",
+            import_hash
+        );
+        let mut rain_document =
+            RainDocument::create(text.clone(), Some(meta_store.clone()), None, None);
+
+        // edits are applied in order and the result is the same as parsing the new text
+        let value_offset = text.find("#value 1").unwrap() + 7;
+        // second edit's range is based on the text after the first edit
+        let main_offset = text.find("opcode-1").unwrap() + 5;
+        let edits = vec![
+            TextEdit {
+                range: [value_offset, value_offset + 1],
+                new_text: "0x1234".to_owned(),
+            },
+            TextEdit {
+                range: [main_offset + 7, main_offset + 8],
+                new_text: "4 undefined".to_owned(),
+            },
+        ];
+        rain_document.apply_edits(edits, None).unwrap();
+        let new_text = text
+            .replace("#value 1", "#value 0x1234")
+            .replace("opcode-1", "opcode-4 undefined");
+        let expected = RainDocument::create(new_text.clone(), Some(meta_store.clone()), None, None);
+        assert_eq!(rain_document, expected);
+
+        // resolved imports are reused, so they are not read from the meta store again
+        rain_document.meta_store = Arc::new(RwLock::new(Store::new()));
+        let edits = vec![TextEdit {
+            range: [new_text.len(), new_text.len()],
+            new_text: "#new-binding 1\n".to_owned(),
+        }];
+        rain_document.apply_edits(edits, None).unwrap();
+        let expected = RainDocument::create(
+            new_text.clone() + "#new-binding 1\n",
+            Some(meta_store),
+            None,
+            None,
+        );
+        assert_eq!(rain_document.imports(), expected.imports());
+        assert_eq!(rain_document.bindings(), expected.bindings());
+        assert_eq!(rain_document.problems(), expected.problems());
+
+        // only the rainlang bindings not depending on the touched ones are reused
+        let mut new_bindings = rain_document.bindings().clone();
+        new_bindings[0].content = "0x01".to_owned();
+        let mut reusable = RainDocument::reusable_bindings(&new_bindings, rain_document.bindings())
+            .into_keys()
+            .collect::<Vec<_>>();
+        reusable.sort();
+        assert!(reusable.is_empty());
+        new_bindings[0].content = "0x1234".to_owned();
+        new_bindings[4].content = "2".to_owned();
+        let mut reusable = RainDocument::reusable_bindings(&new_bindings, rain_document.bindings())
+            .into_keys()
+            .collect::<Vec<_>>();
+        reusable.sort();
+        assert_eq!(reusable, vec!["main", "other"]);
+
+        // out of bounds edits are rejected without changing anything
+        let edits = vec![TextEdit {
+            range: [0, new_text.len() + 100],
+            new_text: String::new(),
+        }];
+        assert!(rain_document.apply_edits(edits, None).is_err());
+        assert_eq!(rain_document.text(), new_text + "#new-binding 1\n");
+
+        // rainlang bindings parsed with rebinds are not reused by a reparse without them
+        let text = "---\n#a 1\n#b\n_: a;\n#c 2";
+        let edits = || {
+            vec![TextEdit {
+                range: [text.len() - 1, text.len()],
+                new_text: "3".to_owned(),
+            }]
+        };
+        let expected = RainDocument::create(text.replace("#c 2", "#c 3"), None, None, None);
+        let mut rain_document = RainDocument::create(
+            text.to_owned(),
+            None,
+            None,
+            Some(vec![Rebind("a".to_owned(), "0x1234".to_owned())]),
+        );
+        rain_document.apply_edits(edits(), None).unwrap();
+        assert_eq!(rain_document, expected);
+
+        // neither are the unparsed rainlang bindings of a parse aborted by invalid rebinds
+        let mut rain_document = RainDocument::create(
+            text.to_owned(),
+            None,
+            None,
+            Some(vec![Rebind("a".to_owned(), "invalid".to_owned())]),
+        );
+        assert!(rain_document
+            .problems()
+            .iter()
+            .any(|v| v.code == ErrorCode::InvalidSuppliedRebindings));
+        rain_document.apply_edits(edits(), None).unwrap();
+        assert_eq!(rain_document, expected);
+    }
 }
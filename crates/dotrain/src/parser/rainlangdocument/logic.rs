@@ -1,5 +1,13 @@
-use rain_metadata::types::authoring::v1::AuthoringMeta;
-use crate::search_binding_ref;
+use rain_metadata::{types::authoring::v1::AuthoringMeta, KnownMagic, RainMetaDocumentV1Item, Store};
+use std::str::FromStr;
+use alloy_primitives::U256;
+use crate::{
+    search_binding_ref,
+    types::{
+        literal::{string_literal_end, LiteralValue},
+        words::{OperandArgKind, WordSpec, WordSpecs},
+    },
+};
 
 use super::*;
 use super::super::{
@@ -7,7 +15,8 @@ use super::super::{
         error::{Error, ErrorCode},
         types::patterns::*,
     },
-    inclusive_parse, fill_in, exclusive_parse, tracked_trim,
+    blank, exclusive_parse, illegal_code_chars, pragma_keywords, split_at, split_whitespace,
+    tokenize, tracked_trim, TokenKind,
 };
 
 impl RainlangDocument {
@@ -15,67 +24,60 @@ impl RainlangDocument {
     pub(super) fn _parse(
         &mut self,
         namespace: &Namespace,
-        authoring_meta: &AuthoringMeta,
+        authoring_meta: Option<&AuthoringMeta>,
+        meta_store: Option<&Arc<RwLock<Store>>>,
     ) -> Result<(), Error> {
         self.reset_state();
         self.ast.clear();
         self.problems.clear();
         self.comments.clear();
         self.error = None;
-        let mut document = self.text.clone();
-
-        // check for illegal characters
-        // ends the parsing if an illegal char is found
-        let illegal_chars = inclusive_parse(&document, &ILLEGAL_CHAR, 0);
-        if !illegal_chars.is_empty() {
-            self.problems.push(ErrorCode::IllegalChar.to_problem(
-                vec![&illegal_chars[0].0],
-                [illegal_chars[0].1[0], illegal_chars[0].1[0]],
-            ));
-            return Ok(());
-        };
 
-        // parse and take out comments
-        for parsed_comment in inclusive_parse(&document, &COMMENT_PATTERN, 0) {
-            if !parsed_comment.0.ends_with("*/") {
+        // check for illegal characters, comments and string literals can contain any character,
+        // each run of illegal characters is reported and parsing continues with them left in
+        // place, so the items they are part of are parsed as erroneous items
+        for illegal_char in illegal_code_chars(&self.text, 0) {
+            self.problems
+                .push(ErrorCode::IllegalChar.to_problem(vec![&illegal_char.0], illegal_char.1));
+        }
+
+        // take out comments from the tokens
+        let tokens = tokenize(&self.text, 0);
+        let comments: Vec<Offsets> = tokens
+            .iter()
+            .filter(|v| v.kind == TokenKind::Comment)
+            .map(|v| v.position)
+            .collect();
+        for position in &comments {
+            let comment = &self.text[position[0]..position[1]];
+            if !comment.ends_with("*/") {
                 self.problems
-                    .push(ErrorCode::UnexpectedEndOfComment.to_problem(vec![], parsed_comment.1));
+                    .push(ErrorCode::UnexpectedEndOfComment.to_problem(vec![], *position));
             }
             self.comments.push(Comment {
-                comment: parsed_comment.0.clone(),
-                position: parsed_comment.1,
+                comment: comment.to_owned(),
+                position: *position,
             });
-            fill_in(&mut document, parsed_comment.1)?;
         }
+        let commentless = blank(&self.text, [0, self.text.len()], &comments, None);
 
-        // parse and take out pragma definitions
-        let pragmas = inclusive_parse(&document, &PRAGMA_PATTERN, 0)
-            .iter()
-            .map(|v| {
-                if v.0 == PRAGMA_KEYWORD {
-                    v.clone()
-                } else {
-                    let mut pos = v.1;
-                    if v.0.starts_with([' ', '\n', '\t', '\r']) {
-                        pos[0] += 1;
-                    }
-                    if v.0.ends_with([' ', '\n', '\t', '\r']) {
-                        pos[1] -= 1;
-                    }
-                    ParsedItem(PRAGMA_KEYWORD.to_owned(), pos)
-                }
-            })
+        // pragma keywords are the keyword words separated from their neighbours by whitespaces
+        // or comments, their sources span up to the next pragma keyword
+        let pragmas = pragma_keywords(&self.text, &tokens)
+            .into_iter()
+            .map(|v| ParsedItem(PRAGMA_KEYWORD.to_owned(), v))
             .collect::<Vec<ParsedItem>>();
+        let mut pragma_ranges = vec![];
         for (i, parsed_pragma_keyword) in pragmas.iter().enumerate() {
             let start = parsed_pragma_keyword.1[1];
             let end = if i == pragmas.len() - 1 {
-                document.len()
+                commentless.len()
             } else {
                 pragmas[i + 1].1[0]
             };
 
             let mut sources = vec![];
-            let sources_text = &document[start..end];
+            let sources_text = &commentless[start..end];
             if let Some(parsed_src_items) = self.parse_range(sources_text, start, false) {
                 for src in parsed_src_items {
                     if !LITERAL_PATTERN.is_match(&src.0) {
@@ -110,15 +112,12 @@ impl RainlangDocument {
             if sources.is_empty() {
                 self.problems
                     .push(ErrorCode::ExpectedLiteral.to_problem(vec![], parsed_pragma_keyword.1));
-                fill_in(&mut document, parsed_pragma_keyword.1)?;
+                pragma_ranges.push(parsed_pragma_keyword.1);
             } else {
-                fill_in(
-                    &mut document,
-                    [
-                        parsed_pragma_keyword.1[0],
-                        sources[sources.len() - 1].0 .1[1],
-                    ],
-                )?;
+                pragma_ranges.push([
+                    parsed_pragma_keyword.1[0],
+                    sources[sources.len() - 1].0 .1[1],
+                ]);
             };
 
             self.pragmas.push(PragmaStatement {
@@ -150,11 +149,31 @@ impl RainlangDocument {
             }
         }
 
-        let mut src_items: Vec<String> = vec![];
+        // take out comments and pragma statements from the text
+        let mut holes = comments.clone();
+        holes.extend(pragma_ranges);
+        holes.sort();
+        let document = blank(&self.text, [0, self.text.len()], &holes, None);
+
+        // words of the text are the known words merged with the words of the pragma hashes
+        let authoring_meta = &self.resolve_words(authoring_meta, meta_store);
+
         let mut src_items_pos: Vec<Offsets> = vec![];
 
         // begin parsing expression sources and cache them
-        let mut parsed_sources = exclusive_parse(&document, &SOURCE_PATTERN, 0, true);
+        // semis and commas of pragma statements are already taken out of the document
+        let delimiters = |kind: TokenKind| {
+            tokens
+                .iter()
+                .filter(|v| {
+                    v.kind == kind && !document[v.position[0]..v.position[1]].trim().is_empty()
+                })
+                .map(|v| v.position)
+                .collect::<Vec<_>>()
+        };
+        let commas = delimiters(TokenKind::Comma);
+        let mut parsed_sources =
+            split_at(&document, [0, document.len()], &delimiters(TokenKind::Semi));
         if parsed_sources[parsed_sources.len() - 1].0.trim().is_empty() {
             parsed_sources.pop();
         } else {
@@ -170,37 +189,39 @@ impl RainlangDocument {
                         .to_problem(vec![], [v.1[1] - trimmed.2, v.1[1] - trimmed.2]),
                 );
             } else {
-                src_items.push(trimmed.0.to_owned());
                 src_items_pos.push([v.1[0] + trimmed.1, v.1[1] - trimmed.2]);
             }
         }
 
         // reserved keywords + authoring meta words + root namespace occupied keys
-        let mut reserved_keys = vec![];
-        reserved_keys.extend(KEYWORDS.iter().map(|v| v.to_string()));
-        reserved_keys.extend(namespace.keys().cloned());
+        let is_reserved = |key: &str| KEYWORDS.contains(&key) || namespace.contains_key(key);
 
-        for (i, src) in src_items.iter().enumerate() {
-            // reserved keys + parsed lhs items of this srouce
-            let mut occupied_keys = reserved_keys.clone();
+        for (i, src_position) in src_items_pos.iter().enumerate() {
+            // parsed lhs items of this srouce
+            let mut occupied_keys = vec![];
 
             let mut ends_diff: Vec<usize> = vec![];
             let mut sub_src_items: Vec<String> = vec![];
             let mut sub_src_items_pos: Vec<Offsets> = vec![];
             self.ast.push(RainlangSource {
                 lines: vec![],
-                position: src_items_pos[i],
+                position: *src_position,
             });
 
             // parse and cache the sub-sources
-            exclusive_parse(src, &SUB_SOURCE_PATTERN, src_items_pos[i][0], true)
-                .iter()
-                .for_each(|v| {
-                    let trimmed = tracked_trim(&v.0);
-                    sub_src_items.push(trimmed.0.to_owned());
-                    sub_src_items_pos.push([v.1[0] + trimmed.1, v.1[1] - trimmed.2]);
-                    ends_diff.push(trimmed.2);
-                });
+            split_at(
+                &document,
+                *src_position,
+                &commas[commas.partition_point(|v| v[0] < src_position[0])
+                    ..commas.partition_point(|v| v[1] <= src_position[1])],
+            )
+            .iter()
+            .for_each(|v| {
+                let trimmed = tracked_trim(&v.0);
+                sub_src_items.push(trimmed.0.to_owned());
+                sub_src_items_pos.push([v.1[0] + trimmed.1, v.1[1] - trimmed.2]);
+                ends_diff.push(trimmed.2);
+            });
 
             for (j, sub_src) in sub_src_items.iter().enumerate() {
                 self.reset_state();
@@ -227,7 +248,7 @@ impl RainlangDocument {
                     }
                     // parse LHS
                     if !lhs.is_empty() {
-                        let lhs_items = inclusive_parse(lhs, &ANY_PATTERN, cursor_offset);
+                        let lhs_items = split_whitespace(lhs, cursor_offset);
                         for item in lhs_items {
                             self.state.aliases.push(Alias {
                                 name: item.0.clone(),
@@ -239,7 +260,7 @@ impl RainlangDocument {
                                     ErrorCode::InvalidWordPattern.to_problem(vec![&item.0], item.1),
                                 );
                             }
-                            if occupied_keys.contains(&item.0) {
+                            if is_reserved(&item.0) || occupied_keys.contains(&item.0) {
                                 self.problems.push(
                                     ErrorCode::DuplicateAlias.to_problem(vec![&item.0], item.1),
                                 );
@@ -302,6 +323,95 @@ impl RainlangDocument {
         Ok(())
     }
 
+    /// Builds the words of this text from the given known words and the authoring metas of
+    /// the pragma hashes (deployer or sub parser metas) found in the given meta store, undefined
+    /// opcodes get reported only if there are known words or pragma words and all of the pragma
+    /// hashes are found, as otherwise words of the text cannot be fully known
+    pub(super) fn resolve_words(
+        &mut self,
+        authoring_meta: Option<&AuthoringMeta>,
+        meta_store: Option<&Arc<RwLock<Store>>>,
+    ) -> AuthoringMeta {
+        let mut words = authoring_meta.cloned();
+        let mut is_complete = true;
+        for (item, value) in self.pragmas.iter().flat_map(|v| &v.sources) {
+            let hash = value.as_ref().unwrap_or(&item.0);
+            match meta_store.and_then(|v| pragma_words(&v.read().unwrap(), hash)) {
+                Some(pragma_words) => {
+                    let words = words.get_or_insert_with(|| AuthoringMeta(vec![]));
+                    for word in pragma_words.0 {
+                        if !words.0.iter().any(|v| v.word == word.word) {
+                            words.0.push(word);
+                        }
+                    }
+                }
+                None => is_complete = false,
+            }
+        }
+        self.state.check_words = is_complete && words.is_some();
+        words.unwrap_or(AuthoringMeta(vec![]))
+    }
+
+    /// Checks the parsed opcodes and lines against the given word specs, fills the outputs and
+    /// operand args names and descriptions of the opcodes with known specs and reports opcodes
+    /// with out of range inputs or invalid operand args and lines with mismatching LHS items and
+    /// RHS outputs, replacing the previous check results, line outputs are not checked if there
+    /// are no word specs or the line has an opcode of unknown spec or an unclosed opcode
+    pub(crate) fn check_word_specs(&mut self, word_specs: &WordSpecs) {
+        self.problems.retain(|v| !WORD_SPEC_CODES.contains(&v.code));
+        let mut problems = vec![];
+        for line in self.ast.iter_mut().flat_map(|v| &mut v.lines) {
+            for node in &mut line.nodes {
+                check_opcode(node, word_specs, &mut problems);
+            }
+            if word_specs.is_empty() {
+                continue;
+            }
+
+            // each RHS item outputs to as many LHS items as its outputs, in order
+            let Some(outputs) = line
+                .nodes
+                .iter()
+                .map(|v| match v {
+                    Node::Opcode(op) => op.output.map(usize::from),
+                    _ => Some(1),
+                })
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let lhs_count = line.aliases.len();
+            let rhs_count = outputs.iter().sum::<usize>();
+            if rhs_count > lhs_count {
+                let mut count = 0;
+                let excess_rhs = line
+                    .nodes
+                    .iter()
+                    .zip(&outputs)
+                    .filter(|(_, output)| {
+                        count += **output;
+                        **output > 0 && count > lhs_count
+                    })
+                    .map(|(node, _)| node.position())
+                    .collect::<Vec<_>>();
+                problems.push(ErrorCode::MismatchRHS.to_problem(
+                    vec![&lhs_count.to_string(), &rhs_count.to_string()],
+                    [excess_rhs[0][0], excess_rhs[excess_rhs.len() - 1][1]],
+                ));
+            } else if rhs_count < lhs_count {
+                let excess_lhs = &line.aliases[rhs_count..];
+                problems.push(ErrorCode::MismatchLHS.to_problem(
+                    vec![&rhs_count.to_string(), &lhs_count.to_string()],
+                    [
+                        excess_lhs[0].position[0],
+                        excess_lhs[excess_lhs.len() - 1].position[1],
+                    ],
+                ));
+            }
+        }
+        self.problems.extend(problems);
+    }
+
     /// Consumes items (separated by defnied boundries) in the text
     pub(super) fn process_rhs(
         &mut self,
@@ -315,7 +425,7 @@ impl RainlangDocument {
             let cursor = offset - exp.len();
             match exp.chars().next().unwrap() {
                 char if char.is_whitespace() => {
-                    exp = exp.split_at(1).1;
+                    exp = exp.split_at(char.len_utf8()).1;
                 }
                 '>' => {
                     self.problems.push(
@@ -441,6 +551,8 @@ impl RainlangDocument {
                                             msg: p.msg.clone(),
                                             position: v.1,
                                             code: p.code,
+                                            severity: p.severity,
+                                            origin: p.origin.clone(),
                                         })
                                         .collect();
                                     self.problems.extend(problems);
@@ -498,7 +610,7 @@ impl RainlangDocument {
         validate: bool,
     ) -> Option<Vec<ParsedItem>> {
         let mut result = vec![];
-        let mut parsed_items = inclusive_parse(text, &ANY_PATTERN, 0);
+        let mut parsed_items = split_whitespace(text, 0);
         let mut iter = parsed_items.iter_mut();
         while let Some(item) = iter.next() {
             if item.0.starts_with('"') && (item.0 == "\"" || !item.0.ends_with('"')) {
@@ -553,6 +665,17 @@ impl RainlangDocument {
         }
     }
 
+    /// parses the given literal text to its typed value and reports the problem if it is invalid
+    fn parse_literal(&mut self, text: &str, position: Offsets) -> Option<LiteralValue> {
+        match LiteralValue::parse(text) {
+            Ok(v) => Some(v),
+            Err(code) => {
+                self.problems.push(code.to_problem(vec![], position));
+                None
+            }
+        }
+    }
+
     /// parses an upcoming word to the corresponding AST node
     pub(super) fn consume(
         &mut self,
@@ -572,13 +695,15 @@ impl RainlangDocument {
             };
         let next_pos = [cursor, cursor + next.len()];
 
-        if next.starts_with('"') && (next == "\"" || !next.ends_with('"')) {
-            match remaining.find('"') {
-                Some(string_literal_end) => {
-                    let consumed = string_literal_end + next.len() + 1;
+        if next.starts_with('"') {
+            match string_literal_end(exp) {
+                Some(consumed) => {
+                    let position = [cursor, cursor + consumed];
+                    let parsed = self.parse_literal(&exp[0..consumed], position);
                     self.update_state(Node::Literal(Literal {
                         value: exp[0..consumed].to_owned(),
-                        position: [cursor, cursor + consumed],
+                        position,
+                        parsed,
                         lhs_alias: None,
                         id: None,
                     }))?;
@@ -592,6 +717,7 @@ impl RainlangDocument {
                     self.update_state(Node::Literal(Literal {
                         value: exp.to_owned(),
                         position: [cursor, cursor + exp.len()],
+                        parsed: None,
                         lhs_alias: None,
                         id: None,
                     }))?;
@@ -606,6 +732,7 @@ impl RainlangDocument {
                     self.update_state(Node::Literal(Literal {
                         value: exp[0..consumed].to_owned(),
                         position: [cursor, cursor + consumed],
+                        parsed: LiteralValue::parse(&exp[0..consumed]).ok(),
                         lhs_alias: None,
                         id: None,
                     }))?;
@@ -619,6 +746,7 @@ impl RainlangDocument {
                     self.update_state(Node::Literal(Literal {
                         value: exp.to_owned(),
                         position: [cursor, cursor + exp.len()],
+                        parsed: None,
                         lhs_alias: None,
                         id: None,
                     }))?;
@@ -649,6 +777,9 @@ impl RainlangDocument {
                     .push(ErrorCode::InvalidWordPattern.to_problem(vec![next], next_pos));
             } else if let Some(word) = authoring_meta.0.iter().find(|&v| v.word.as_str() == next) {
                 op.opcode.description = word.description.clone();
+            } else if self.state.check_words {
+                self.problems
+                    .push(ErrorCode::UndefinedWord.to_problem(vec![next], next_pos));
             }
 
             if remaining.starts_with('<') {
@@ -678,9 +809,11 @@ impl RainlangDocument {
                     .push(ErrorCode::ExpectedOpeningParen.to_problem(vec![], next_pos));
             }
         } else if LITERAL_PATTERN.is_match(next) {
+            let parsed = self.parse_literal(next, next_pos);
             self.update_state(Node::Literal(Literal {
                 value: next.to_owned(),
                 position: next_pos,
+                parsed,
                 lhs_alias: None,
                 id: None,
             }))?;
@@ -689,10 +822,12 @@ impl RainlangDocument {
                 match &b.item {
                     BindingItem::Literal(c) => {
                         let value = c.value.to_owned();
+                        let parsed = c.parsed.clone();
                         self.update_state(Node::Literal(Literal {
                             id: Some(next.to_owned()),
                             value,
                             position: next_pos,
+                            parsed,
                             lhs_alias: None,
                         }))?;
                     }
@@ -752,6 +887,7 @@ impl RainlangDocument {
                             self.update_state(Node::Literal(Literal {
                                 value: c.value.clone(),
                                 position: next_pos,
+                                parsed: c.parsed.clone(),
                                 lhs_alias: None,
                                 id: Some(next.to_owned()),
                             }))?;
@@ -899,3 +1035,117 @@ impl RainlangDocument {
         }
     }
 }
+
+/// Finds the words of the given pragma hash in the given meta store, either from the
+/// authoring meta of a deployer or from a sub parser's authoring meta
+pub(crate) fn pragma_words(meta_store: &Store, hash: &str) -> Option<AuthoringMeta> {
+    let hash_bytes = alloy_primitives::hex::decode(hash).ok()?;
+    if let Some(deployer) = meta_store.get_deployer(&hash_bytes) {
+        return deployer.authoring_meta.clone();
+    }
+    RainMetaDocumentV1Item::cbor_decode(meta_store.get_meta(&hash_bytes)?)
+        .ok()?
+        .into_iter()
+        .find(|v| v.magic == KnownMagic::AuthoringMetaV1)
+        .and_then(|v| AuthoringMeta::try_from(v).ok())
+}
+
+/// Checks the given node and its nested opcodes against their word spec, the operand args are
+/// checked for all opcodes while the inputs and output only for the closed ones
+fn check_opcode(node: &mut Node, word_specs: &WordSpecs, problems: &mut Vec<Problem>) {
+    if let Node::Opcode(op) = node {
+        for input in &mut op.inputs {
+            check_opcode(input, word_specs, problems);
+        }
+        let spec = word_specs.get(&op.opcode.name);
+        check_operand_args(op, spec, problems);
+        op.output = None;
+        if op.position[1] == 0 {
+            return;
+        }
+        if let Some(spec) = spec {
+            op.output = Some(spec.outputs);
+            if !spec.accepts_inputs(op.inputs.len()) {
+                problems.push(ErrorCode::OutOfRangeInputs.to_problem(
+                    vec![
+                        &spec.inputs_range(),
+                        &op.opcode.name,
+                        &op.inputs.len().to_string(),
+                    ],
+                    op.opcode.position,
+                ));
+            }
+        }
+    }
+}
+
+/// Names and describes the operand args of the given opcode from its word spec and reports
+/// the mismatching count, kinds and out of range values of them
+fn check_operand_args(op: &mut Opcode, spec: Option<&WordSpec>, problems: &mut Vec<Problem>) {
+    let arg_specs = spec.and_then(|v| v.operand_args.as_ref());
+    let position = op
+        .operand_args
+        .as_ref()
+        .map_or(op.opcode.position, |v| v.position);
+    let args = op
+        .operand_args
+        .as_mut()
+        .map_or(&mut [][..], |v| &mut v.args);
+    for (i, arg) in args.iter_mut().enumerate() {
+        if let Some(arg_spec) = arg_specs.and_then(|v| v.get(i)) {
+            arg.name = arg_spec.name.clone();
+            arg.description = arg_spec.description.clone();
+        } else {
+            arg.name = "operand arg".to_owned();
+            arg.description = String::new();
+        }
+    }
+    let (Some(spec), Some(arg_specs)) = (spec, arg_specs) else {
+        return;
+    };
+    if !spec.accepts_operand_args(args.len()) {
+        problems.push(ErrorCode::MismatchOperandArgs.to_problem(
+            vec![
+                &spec.operand_args_range(),
+                &op.opcode.name,
+                &args.len().to_string(),
+            ],
+            position,
+        ));
+    }
+    for (arg, arg_spec) in args.iter().zip(arg_specs) {
+        let is_quote = arg
+            .binding_id
+            .as_ref()
+            .is_some_and(|(id, is_quote_binding)| id.starts_with('\'') || *is_quote_binding);
+        let arg_text = arg
+            .binding_id
+            .as_ref()
+            .map_or(arg.value.as_deref().unwrap_or_default(), |v| v.0.as_str());
+        match arg_spec.kind {
+            OperandArgKind::Literal if is_quote => {
+                problems.push(
+                    ErrorCode::InvalidOperandArgKind
+                        .to_problem(vec![arg_text, "literal", &arg_spec.name], arg.position),
+                );
+            }
+            OperandArgKind::Quote if !is_quote => {
+                problems.push(
+                    ErrorCode::InvalidOperandArgKind
+                        .to_problem(vec![arg_text, "quote", &arg_spec.name], arg.position),
+                );
+            }
+            OperandArgKind::Literal => {
+                if let Some(value) = arg.value.as_ref().and_then(|v| U256::from_str(v).ok()) {
+                    if !arg_spec.accepts_value(value) {
+                        problems.push(ErrorCode::OutOfRangeOperandArgs.to_problem(
+                            vec![&arg_spec.name, &arg_spec.value_range()],
+                            arg.position,
+                        ));
+                    }
+                }
+            }
+            OperandArgKind::Quote => {}
+        }
+    }
+}
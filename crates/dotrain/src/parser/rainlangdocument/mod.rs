@@ -1,6 +1,7 @@
+use std::sync::{Arc, RwLock};
 use serde::{Serialize, Deserialize};
 use super::super::{types::ast::*, error::ErrorCode};
-use rain_metadata::types::authoring::v1::AuthoringMeta;
+use rain_metadata::{types::authoring::v1::AuthoringMeta, Store};
 
 #[cfg(feature = "js-api")]
 use tsify::Tsify;
@@ -19,8 +20,20 @@ struct RainlangState {
     aliases: Vec<Alias>,
     parens: Parens,
     depth: usize,
+    /// if all the words available to the text are known, only then undefined opcodes are reported
+    check_words: bool,
 }
 
+/// Error codes of the problems reported by checking the opcodes against the word specs
+pub(crate) const WORD_SPEC_CODES: [ErrorCode; 6] = [
+    ErrorCode::MismatchRHS,
+    ErrorCode::MismatchLHS,
+    ErrorCode::OutOfRangeInputs,
+    ErrorCode::MismatchOperandArgs,
+    ErrorCode::OutOfRangeOperandArgs,
+    ErrorCode::InvalidOperandArgKind,
+];
+
 /// Data structure (parse tree) of a Rainlang text
 ///
 /// RainlangDocument represents the parse tree of a Rainlang text which is used by the
@@ -68,11 +81,13 @@ impl RainlangDocument {
 }
 
 impl RainlangDocument {
-    /// Creates a new instance
+    /// Creates a new instance, the words of the text are the given known words merged with
+    /// the words of its pragma hashes that are found in the given meta store
     pub(crate) fn create(
         text: String,
         namespace: &Namespace,
         authoring_meta: Option<&AuthoringMeta>,
+        meta_store: Option<&Arc<RwLock<Store>>>,
     ) -> RainlangDocument {
         let mut rainlang_doc = RainlangDocument {
             text,
@@ -84,7 +99,7 @@ impl RainlangDocument {
             error: None,
             state: RainlangState::default(),
         };
-        rainlang_doc.parse(namespace, authoring_meta.unwrap_or(&AuthoringMeta(vec![])));
+        rainlang_doc.parse(namespace, authoring_meta, meta_store);
         rainlang_doc
     }
 
@@ -101,8 +116,13 @@ impl RainlangDocument {
         }
     }
 
-    pub(crate) fn parse(&mut self, namespace: &Namespace, authoring_meta: &AuthoringMeta) {
-        if let Err(e) = self._parse(namespace, authoring_meta) {
+    pub(crate) fn parse(
+        &mut self,
+        namespace: &Namespace,
+        authoring_meta: Option<&AuthoringMeta>,
+        meta_store: Option<&Arc<RwLock<Store>>>,
+    ) {
+        if let Err(e) = self._parse(namespace, authoring_meta, meta_store) {
             self.error = Some(e.to_string());
             self.problems
                 .push(ErrorCode::RuntimeError.to_problem(vec![&e.to_string()], [0, 0]));
@@ -115,6 +135,10 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
     use rain_metadata::types::authoring::v1::AuthoringMetaItem;
+    use crate::types::{
+        literal::LiteralValue,
+        words::{OperandArgKind, OperandArgSpec, WordSpec, WordSpecs},
+    };
 
     #[test]
     fn test_process_opcode_method() {
@@ -124,6 +148,7 @@ mod tests {
         let value_node = Node::Literal(Literal {
             value: "12".to_owned(),
             position: [3, 4],
+            parsed: None,
             lhs_alias: None,
             id: None,
         });
@@ -141,12 +166,14 @@ mod tests {
                 Node::Literal(Literal {
                     value: "1".to_owned(),
                     position: [9, 10],
+                    parsed: None,
                     lhs_alias: None,
                     id: None,
                 }),
                 Node::Literal(Literal {
                     value: "2".to_owned(),
                     position: [11, 12],
+                    parsed: None,
                     lhs_alias: None,
                     id: None,
                 }),
@@ -171,12 +198,14 @@ mod tests {
                 Node::Literal(Literal {
                     value: "1".to_owned(),
                     position: [9, 10],
+                    parsed: None,
                     lhs_alias: None,
                     id: None,
                 }),
                 Node::Literal(Literal {
                     value: "2".to_owned(),
                     position: [11, 12],
+                    parsed: None,
                     lhs_alias: None,
                     id: None,
                 }),
@@ -700,6 +729,227 @@ mod tests {
         assert_eq!(rl.state.nodes, expected_state_nodes);
     }
 
+    #[test]
+    fn test_check_word_specs_method() {
+        let spec = |inputs: u8, max_inputs: Option<u8>, outputs: u8| WordSpec {
+            inputs,
+            max_inputs,
+            outputs,
+            operand_args: None,
+        };
+        let word_specs = WordSpecs(HashMap::from([
+            ("add".to_owned(), spec(2, None, 1)),
+            ("sub".to_owned(), spec(2, Some(15), 1)),
+            ("block-timestamp".to_owned(), spec(0, None, 1)),
+            ("ensure".to_owned(), spec(1, None, 0)),
+        ]));
+        let text = r"_: add(1 2 3),
+a b: add(1 2),
+c: sub(1 2 3) 4,
+d e f: block-timestamp() 5,
+: ensure(6),
+x: unknown(7) 8;";
+        let mut rl = RainlangDocument::create(text.to_owned(), &HashMap::new(), None, None);
+        rl.check_word_specs(&word_specs);
+        let pos = |item: &str| {
+            let start = text.find(item).unwrap();
+            [start, start + item.len()]
+        };
+        let expected_problems = vec![
+            ErrorCode::OutOfRangeInputs.to_problem(vec!["2", "add", "3"], pos("add")),
+            ErrorCode::MismatchLHS.to_problem(vec!["1", "2"], pos("b")),
+            ErrorCode::MismatchRHS.to_problem(vec!["1", "2"], pos("4")),
+            ErrorCode::MismatchLHS.to_problem(vec!["2", "3"], pos("f")),
+        ];
+        assert_eq!(rl.problems, expected_problems);
+
+        // outputs of opcodes are filled from their spec
+        let outputs = rl.ast[0]
+            .lines
+            .iter()
+            .filter_map(|v| match &v.nodes[0] {
+                Node::Opcode(op) => Some(op.output),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outputs,
+            vec![Some(1), Some(1), Some(1), Some(1), Some(0), None]
+        );
+
+        // checking again replaces the previous results
+        rl.check_word_specs(&WordSpecs::default());
+        assert!(rl.problems.is_empty());
+    }
+
+    #[test]
+    fn test_check_operand_args() {
+        let arg =
+            |name: &str, kind: OperandArgKind, max: Option<u64>, optional: bool| OperandArgSpec {
+                name: name.to_owned(),
+                description: format!("{} description", name),
+                kind,
+                min: None,
+                max,
+                optional,
+            };
+        let spec = |operand_args: Vec<OperandArgSpec>| WordSpec {
+            inputs: 0,
+            max_inputs: None,
+            outputs: 1,
+            operand_args: Some(operand_args),
+        };
+        let word_specs = WordSpecs(HashMap::from([
+            (
+                "read".to_owned(),
+                spec(vec![
+                    arg("index", OperandArgKind::Literal, Some(255), false),
+                    arg("offset", OperandArgKind::Literal, Some(10), true),
+                ]),
+            ),
+            (
+                "call".to_owned(),
+                spec(vec![arg("source", OperandArgKind::Quote, None, false)]),
+            ),
+            ("now".to_owned(), spec(vec![])),
+        ]));
+        let binding = |name: &str, item: BindingItem| {
+            NamespaceItem::Leaf(NamespaceLeaf {
+                hash: String::new(),
+                import_index: -1,
+                element: Binding {
+                    name: name.to_owned(),
+                    name_position: [0, 0],
+                    content: String::new(),
+                    content_position: [0, 0],
+                    position: [0, 0],
+                    problems: vec![],
+                    item,
+                },
+            })
+        };
+        let namespace: Namespace = HashMap::from([
+            (
+                "lit".to_owned(),
+                binding(
+                    "lit",
+                    BindingItem::Literal(LiteralBindingItem {
+                        value: "0x012c".to_owned(),
+                        parsed: LiteralValue::parse("0x012c").ok(),
+                    }),
+                ),
+            ),
+            (
+                "other".to_owned(),
+                binding("other", BindingItem::Exp(RainlangDocument::new())),
+            ),
+        ]);
+        let text = r"_: read<1 0xff>(),
+_: read<256>(),
+_: read<lit>(),
+_: read(),
+_: call<'other>(),
+_: call<1>(),
+_: read<'other>(),
+_: now<1>();";
+        let mut rl = RainlangDocument::create(text.to_owned(), &namespace, None, None);
+        rl.check_word_specs(&word_specs);
+        let pos = |item: &str, offset: usize, len: usize| {
+            let start = text.find(item).unwrap() + offset;
+            [start, start + len]
+        };
+        let expected_problems = vec![
+            ErrorCode::OutOfRangeOperandArgs
+                .to_problem(vec!["offset", "at most 10"], pos("0xff", 0, 4)),
+            ErrorCode::OutOfRangeOperandArgs
+                .to_problem(vec!["index", "at most 255"], pos("256", 0, 3)),
+            ErrorCode::OutOfRangeOperandArgs
+                .to_problem(vec!["index", "at most 255"], pos("lit", 0, 3)),
+            ErrorCode::MismatchOperandArgs
+                .to_problem(vec!["1 to 2", "read", "0"], pos("read()", 0, 4)),
+            ErrorCode::InvalidOperandArgKind
+                .to_problem(vec!["1", "quote", "source"], pos("call<1>", 5, 1)),
+            ErrorCode::InvalidOperandArgKind.to_problem(
+                vec!["'other", "literal", "index"],
+                pos("read<'other>", 5, 6),
+            ),
+            ErrorCode::MismatchOperandArgs.to_problem(vec!["0", "now", "1"], pos("now<1>", 3, 3)),
+        ];
+        assert_eq!(rl.problems, expected_problems);
+
+        // operand args are named and described from their spec
+        let Node::Opcode(op) = &rl.ast[0].lines[0].nodes[0] else {
+            panic!("expected opcode")
+        };
+        let args = op.operand_args.as_ref().unwrap().args.iter();
+        assert_eq!(
+            args.map(|v| (v.name.as_str(), v.description.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("index", "index description"),
+                ("offset", "offset description")
+            ]
+        );
+
+        // without specs the operand args get their default name back
+        rl.check_word_specs(&WordSpecs::default());
+        assert!(rl.problems.is_empty());
+        let Node::Opcode(op) = &rl.ast[0].lines[0].nodes[0] else {
+            panic!("expected opcode")
+        };
+        assert!(op
+            .operand_args
+            .as_ref()
+            .unwrap()
+            .args
+            .iter()
+            .all(|v| v.name == "operand arg" && v.description.is_empty()));
+    }
+
+    #[test]
+    fn test_pragmas() {
+        let text = "/* words */using-words-from 0x1234 /* deployer */ 0xabcd\n_: 1;";
+        let rl = RainlangDocument::create(text.to_owned(), &HashMap::new(), None, None);
+        let pos = |text: &str, item: &str| {
+            let start = text.find(item).unwrap();
+            [start, start + item.len()]
+        };
+        assert_eq!(
+            rl.pragmas,
+            vec![PragmaStatement {
+                keyword: pos(text, "using-words-from"),
+                sources: vec![
+                    (ParsedItem("0x1234".to_owned(), pos(text, "0x1234")), None),
+                    (ParsedItem("0xabcd".to_owned(), pos(text, "0xabcd")), None),
+                ],
+            }]
+        );
+        assert!(rl.problems.is_empty());
+        assert_eq!(rl.ast.len(), 1);
+        assert_eq!(rl.ast[0].position, pos(text, "_: 1"));
+
+        // the keyword must be separated from its neighbours by whitespaces or comments
+        let text = "_: 1;using-words-from 0x1234;";
+        let rl = RainlangDocument::create(text.to_owned(), &HashMap::new(), None, None);
+        assert!(rl.pragmas.is_empty());
+        assert_eq!(
+            rl.problems,
+            vec![ErrorCode::InvalidExpression
+                .to_problem(vec![], pos(text, "using-words-from 0x1234"))]
+        );
+
+        // only one pragma statement is allowed
+        let text = "using-words-from 0x1234 using-words-from\n0xabcd _: 1;";
+        let rl = RainlangDocument::create(text.to_owned(), &HashMap::new(), None, None);
+        assert_eq!(rl.pragmas.len(), 2);
+        assert_eq!(
+            rl.problems,
+            vec![ErrorCode::UnexpectedPragma
+                .to_problem(vec![], pos(text, "using-words-from\n0xabcd"))]
+        );
+        assert_eq!(rl.ast[0].position, pos(text, "_: 1"));
+    }
+
     #[test]
     fn test_search_namespace_method() {
         let mut rl = RainlangDocument::new();
@@ -735,6 +985,7 @@ mod tests {
             // dependencies: vec![],
             item: BindingItem::Literal(LiteralBindingItem {
                 value: "1234".to_owned(),
+                parsed: LiteralValue::parse("1234").ok(),
             }),
         };
         let deep_leaf = NamespaceItem::Leaf(NamespaceLeaf {
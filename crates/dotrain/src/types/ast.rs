@@ -1,6 +1,7 @@
 //! All data types of RainDocument/RainlangDocument parse tree
 
 use std::collections::HashMap;
+use super::literal::LiteralValue;
 use super::super::error::ErrorCode;
 use serde::{Serialize, Deserialize};
 use super::super::parser::{rainlangdocument::RainlangDocument, raindocument::RainDocument};
@@ -17,6 +18,18 @@ pub type Offsets = [usize; 2];
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct ParsedItem(pub String, pub Offsets);
 
+/// Severity of a problem, only problems with error severity make a RainDocument uncomposable
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
 /// Type for encountered problem within the text
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
@@ -24,6 +37,25 @@ pub struct Problem {
     pub msg: String,
     pub position: Offsets,
     pub code: ErrorCode,
+    #[serde(default)]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub severity: Severity,
+    /// chain of imported dotrains leading to the actual location of the problem
+    /// if it is originated from an import
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub origin: Option<Vec<ProblemOrigin>>,
+}
+
+/// Type for a location in an imported dotrain that a problem is originated from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ProblemOrigin {
+    pub hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub uri: Option<String>,
+    pub position: Offsets,
 }
 
 /// Type for AST Value node
@@ -33,6 +65,10 @@ pub struct Problem {
 pub struct Literal {
     pub value: String,
     pub position: Offsets,
+    /// typed value of the literal, not present if the literal is invalid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub parsed: Option<LiteralValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "js-api", tsify(optional))]
     pub lhs_alias: Option<Vec<Alias>>,
@@ -75,7 +111,10 @@ pub struct OperandArg {
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct Opcode {
     pub opcode: OpcodeDetails,
+    /// Encoded operand, not computed as word specs do not describe how the operand args are
+    /// packed into the operand, the parsed args are available in `operand_args`
     pub operand: Option<u8>,
+    /// Number of outputs of the opcode from its word spec, `None` if the word has no spec
     pub output: Option<u8>,
     pub position: Offsets,
     pub parens: Offsets,
@@ -207,6 +246,10 @@ pub struct ElidedBindingItem {
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct LiteralBindingItem {
     pub value: String,
+    /// typed value of the literal, not present if the literal is invalid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub parsed: Option<LiteralValue>,
 }
 
 /// Type of a quote binding
@@ -220,6 +263,7 @@ pub struct QuoteBindingItem {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+#[allow(clippy::large_enum_variant)]
 pub enum BindingItem {
     Elided(ElidedBindingItem),
     Literal(LiteralBindingItem),
@@ -329,6 +373,7 @@ impl NamespaceLeaf {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 #[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+#[allow(clippy::large_enum_variant)]
 pub enum NamespaceItem {
     Leaf(NamespaceLeaf),
     Node(Namespace),
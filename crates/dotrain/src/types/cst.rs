@@ -0,0 +1,224 @@
+//! All data types of the lossless concrete syntax tree (CST) of RainDocument/RainlangDocument texts
+//!
+//! Every byte of a text belongs to exactly one token of its CST, either a significant token or a
+//! trivia (whitespace and comments), so the text can be reproduced from the tree as it was. Nodes
+//! span from their first to their last significant token, trivia around them belongs to their parent.
+//! Positions are the same as the positions of the typed AST items, so an AST item can be looked up
+//! in the CST by its position, see [SyntaxNode::node_at()].
+
+use std::fmt::Display;
+use serde::{Serialize, Deserialize};
+use super::ast::Offsets;
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Kind of a CST node or token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum SyntaxKind {
+    // nodes
+    /// root of a RainDocument text
+    Document,
+    /// front matter of a RainDocument, including the `---` separator
+    FrontMatter,
+    /// an import statement
+    Import,
+    /// configurations of an import statement
+    ImportConfig,
+    /// a binding
+    Binding,
+    /// body of an elided binding
+    ElidedBody,
+    /// body of a literal binding
+    LiteralBody,
+    /// body of a quote binding
+    QuoteBody,
+    /// root of a rainlang text or body of a rainlang binding
+    Rainlang,
+    /// a pragma statement
+    Pragma,
+    /// a rainlang source, delimited by `;`
+    Source,
+    /// a rainlang line, delimited by `,`
+    Line,
+    /// left hand side of a rainlang line
+    Lhs,
+    /// right hand side of a rainlang line
+    Rhs,
+    /// an opcode with its operand args and inputs
+    Opcode,
+    /// operand args of an opcode
+    OperandArgs,
+
+    // tokens
+    /// a run of whitespaces
+    Whitespace,
+    /// a comment
+    Comment,
+    /// front matter content
+    FrontMatterText,
+    /// `---`
+    Separator,
+    /// `@`
+    At,
+    /// `#`
+    HashSign,
+    /// `;`
+    Semi,
+    /// `,`
+    Comma,
+    /// `:`
+    Colon,
+    /// `(`
+    LParen,
+    /// `)`
+    RParen,
+    /// `<`
+    LAngle,
+    /// `>`
+    RAngle,
+    /// name of an import or a binding
+    Name,
+    /// meta hash of an import
+    MetaHash,
+    /// a literal value
+    Literal,
+    /// any other word, such as opcodes, aliases, quotes and import configurations
+    Word,
+}
+
+impl SyntaxKind {
+    /// Determines if this kind is a trivia, ie whitespace or comment
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, SyntaxKind::Whitespace | SyntaxKind::Comment)
+    }
+}
+
+/// Type of a CST leaf
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct SyntaxToken {
+    pub kind: SyntaxKind,
+    pub text: String,
+    pub position: Offsets,
+}
+
+/// Type of a CST node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub position: Offsets,
+    pub children: Vec<SyntaxElement>,
+}
+
+/// Type of a CST element, either a node or a token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            SyntaxElement::Node(v) => v.kind,
+            SyntaxElement::Token(v) => v.kind,
+        }
+    }
+
+    pub fn position(&self) -> Offsets {
+        match self {
+            SyntaxElement::Node(v) => v.position,
+            SyntaxElement::Token(v) => v.position,
+        }
+    }
+
+    pub fn as_node(&self) -> Option<&SyntaxNode> {
+        match self {
+            SyntaxElement::Node(v) => Some(v),
+            SyntaxElement::Token(_) => None,
+        }
+    }
+
+    pub fn as_token(&self) -> Option<&SyntaxToken> {
+        match self {
+            SyntaxElement::Node(_) => None,
+            SyntaxElement::Token(v) => Some(v),
+        }
+    }
+}
+
+impl SyntaxNode {
+    /// Child nodes of this node
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|v| v.as_node())
+    }
+
+    /// Child tokens of this node
+    pub fn child_tokens(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.children.iter().filter_map(|v| v.as_token())
+    }
+
+    /// First child node of the given kind
+    pub fn child_node(&self, kind: SyntaxKind) -> Option<&SyntaxNode> {
+        self.child_nodes().find(|v| v.kind == kind)
+    }
+
+    /// First child token of the given kind
+    pub fn child_token(&self, kind: SyntaxKind) -> Option<&SyntaxToken> {
+        self.child_tokens().find(|v| v.kind == kind)
+    }
+
+    /// All the tokens of this node's subtree in order
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = vec![];
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(v) => tokens.extend(v.tokens()),
+                SyntaxElement::Token(v) => tokens.push(v),
+            }
+        }
+        tokens
+    }
+
+    /// All the nodes of the given kind in this node's subtree (including itself) in order
+    pub fn descendants(&self, kind: SyntaxKind) -> Vec<&SyntaxNode> {
+        let mut nodes = vec![];
+        if self.kind == kind {
+            nodes.push(self);
+        }
+        for child in self.child_nodes() {
+            nodes.extend(child.descendants(kind));
+        }
+        nodes
+    }
+
+    /// The deepest node of the given kind in this node's subtree that contains the given offset
+    pub fn node_at(&self, offset: usize, kind: SyntaxKind) -> Option<&SyntaxNode> {
+        if offset < self.position[0] || offset > self.position[1] {
+            return None;
+        }
+        self.child_nodes()
+            .find_map(|v| v.node_at(offset, kind))
+            .or(if self.kind == kind { Some(self) } else { None })
+    }
+
+    /// The token in this node's subtree that contains the given offset
+    pub fn token_at(&self, offset: usize) -> Option<&SyntaxToken> {
+        self.tokens()
+            .into_iter()
+            .find(|v| v.position[0] <= offset && offset < v.position[1])
+    }
+}
+
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for token in self.tokens() {
+            f.write_str(&token.text)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,330 @@
+//! Typed values of rainlang literals, parsed from their text according to rainlang's
+//! literal rules so that invalid or out of range values can be reported as problems
+
+use std::str::FromStr;
+use alloy_primitives::{hex, Bytes, U256};
+use serde::{Serialize, Deserialize};
+use super::{
+    super::error::ErrorCode,
+    patterns::{E_PATTERN, HEX_PATTERN, INT_PATTERN},
+};
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Maximum coefficient of a decimal value, rainlang packs decimal values into 256 bits as
+/// a signed 224 bits coefficient and a signed 32 bits exponent
+pub const MAX_COEFFICIENT: U256 =
+    U256::from_limbs([u64::MAX, u64::MAX, u64::MAX, u32::MAX as u64 >> 1]);
+
+/// Maximum number of bytes of a hex literal
+pub const MAX_HEX_BYTES: usize = 32;
+
+/// Maximum number of bytes of a string literal's decoded value, ie rainlang short strings
+pub const MAX_STRING_BYTES: usize = 31;
+
+/// Typed value of a literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum LiteralValue {
+    /// bytes of a hex literal, such as `0xabcd`
+    Hex(#[cfg_attr(feature = "js-api", tsify(type = "string"))] Bytes),
+    /// value of an integer literal, such as `123`
+    Integer(#[cfg_attr(feature = "js-api", tsify(type = "string"))] U256),
+    /// value of a decimal or e notation literal, such as `1.5` or `15e-1`, as a coefficient
+    /// without trailing zeros and a base 10 exponent, so the same value written in different
+    /// forms is equal, eg `1e18` and `10e17`
+    Decimal {
+        #[cfg_attr(feature = "js-api", tsify(type = "string"))]
+        coefficient: U256,
+        exponent: i32,
+    },
+    /// decoded content of a string literal, such as `"some \"text\""`
+    String(String),
+    /// content of a sub parser literal, such as `[some-syntax]`
+    SubParser(String),
+}
+
+impl LiteralValue {
+    /// Parses the given literal text to its typed value, returns the error code of the
+    /// problem if the text is not a valid rainlang literal or its value is out of range
+    pub fn parse(text: &str) -> Result<LiteralValue, ErrorCode> {
+        if text.starts_with('"') {
+            if string_literal_end(text) != Some(text.len()) {
+                return Err(ErrorCode::UnexpectedStringLiteralEnd);
+            }
+            let value = unescape(&text[1..text.len() - 1])?;
+            if value.len() > MAX_STRING_BYTES {
+                return Err(ErrorCode::OutOfRangeStringLength);
+            }
+            Ok(LiteralValue::String(value))
+        } else if let Some(content) = text.strip_prefix('[') {
+            content
+                .strip_suffix(']')
+                .map(|v| LiteralValue::SubParser(v.to_owned()))
+                .ok_or(ErrorCode::UnexpectedSubParserEnd)
+        } else if HEX_PATTERN.is_match(text) {
+            if text.len() % 2 == 1 {
+                return Err(ErrorCode::OddLenHex);
+            }
+            let bytes = hex::decode(text).map_err(|_| ErrorCode::ExpectedHexLiteral)?;
+            if bytes.len() > MAX_HEX_BYTES {
+                return Err(ErrorCode::OutOfRangeValue);
+            }
+            Ok(LiteralValue::Hex(bytes.into()))
+        } else if INT_PATTERN.is_match(text) && !text.contains('.') {
+            U256::from_str_radix(text, 10)
+                .map(LiteralValue::Integer)
+                .map_err(|_| ErrorCode::OutOfRangeValue)
+        } else if INT_PATTERN.is_match(text) || E_PATTERN.is_match(text) {
+            parse_decimal(text)
+        } else {
+            Err(ErrorCode::ExpectedLiteral)
+        }
+    }
+}
+
+/// Finds the end of the string literal at the start of the given text, ie the byte offset right
+/// after its closing quote, a quote that is escaped by a backslash does not end the string literal
+pub fn string_literal_end(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Decodes the escape sequences of a string literal's content, ie `\"`, `\\`, `\n` and hex
+/// escapes of ascii characters such as `\x41`
+fn unescape(content: &str) -> Result<String, ErrorCode> {
+    let mut value = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => value.push('"'),
+            Some('\\') => value.push('\\'),
+            Some('n') => value.push('\n'),
+            Some('x') => {
+                let digits = chars.by_ref().take(2).collect::<String>();
+                let byte = u8::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|v| {
+                        digits.len() == 2
+                            && digits.chars().all(|c| c.is_ascii_hexdigit())
+                            && v.is_ascii()
+                    })
+                    .ok_or(ErrorCode::InvalidEscapeSequence)?;
+                value.push(byte as char);
+            }
+            _ => return Err(ErrorCode::InvalidEscapeSequence),
+        }
+    }
+    Ok(value)
+}
+
+/// Parses a decimal or e notation text to its decimal value, the coefficient must fit in 224 bits
+/// without losing precision, the exponent must fit in 32 bits and the value scaled by the exponent
+/// must fit in 256 bits
+fn parse_decimal(text: &str) -> Result<LiteralValue, ErrorCode> {
+    let (mantissa, exponent) = match text.split_once('e') {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            i64::from_str(exponent).map_err(|_| ErrorCode::OutOfRangeValue)?,
+        ),
+        None => (text, 0),
+    };
+    let (int, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    // leading and trailing zeros of the digits do not add to the precision of the value
+    let digits = int.to_owned() + fraction;
+    let digits = digits.trim_start_matches('0');
+    let significant = digits.trim_end_matches('0');
+    if significant.is_empty() {
+        return Ok(LiteralValue::Decimal {
+            coefficient: U256::ZERO,
+            exponent: 0,
+        });
+    }
+    let coefficient = U256::from_str_radix(significant, 10)
+        .ok()
+        .filter(|v| *v <= MAX_COEFFICIENT)
+        .ok_or(ErrorCode::OutOfRangePrecision)?;
+    let exponent = exponent
+        .saturating_sub(fraction.len() as i64)
+        .saturating_add((digits.len() - significant.len()) as i64);
+    if exponent > 0 {
+        u64::try_from(exponent)
+            .ok()
+            .and_then(|v| U256::from(10).checked_pow(U256::from(v)))
+            .and_then(|v| coefficient.checked_mul(v))
+            .ok_or(ErrorCode::OutOfRangeValue)?;
+    }
+    let exponent = i32::try_from(exponent).map_err(|_| ErrorCode::OutOfRangeValue)?;
+    Ok(LiteralValue::Decimal {
+        coefficient,
+        exponent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_literal_value() {
+        let decimal = |coefficient: u64, exponent: i32| LiteralValue::Decimal {
+            coefficient: U256::from(coefficient),
+            exponent,
+        };
+
+        assert_eq!(
+            LiteralValue::parse("0x00ff"),
+            Ok(LiteralValue::Hex(vec![0, 255].into()))
+        );
+        assert_eq!(LiteralValue::parse("0x0ff"), Err(ErrorCode::OddLenHex));
+        assert!(LiteralValue::parse(&("0x".to_owned() + &"ab".repeat(32))).is_ok());
+        assert_eq!(
+            LiteralValue::parse(&("0x".to_owned() + &"ab".repeat(33))),
+            Err(ErrorCode::OutOfRangeValue)
+        );
+
+        assert_eq!(
+            LiteralValue::parse("123"),
+            Ok(LiteralValue::Integer(U256::from(123)))
+        );
+        assert_eq!(
+            LiteralValue::parse(&U256::MAX.to_string()),
+            Ok(LiteralValue::Integer(U256::MAX))
+        );
+        assert_eq!(
+            LiteralValue::parse(&(U256::MAX.to_string() + "0")),
+            Err(ErrorCode::OutOfRangeValue)
+        );
+
+        assert_eq!(LiteralValue::parse("1.5"), Ok(decimal(15, -1)));
+        assert_eq!(LiteralValue::parse("0012.300"), Ok(decimal(123, -1)));
+        assert_eq!(LiteralValue::parse("2.5e16"), Ok(decimal(25, 15)));
+        assert_eq!(LiteralValue::parse("8.3e-2"), Ok(decimal(83, -3)));
+        assert_eq!(LiteralValue::parse("123.456e12"), Ok(decimal(123456, 9)));
+        assert_eq!(LiteralValue::parse("0.000"), Ok(decimal(0, 0)));
+
+        // the same value in decimal and e notation forms
+        for (decimal, others) in [
+            ("1.0e18", vec!["1e18", "10e17"]),
+            ("15.0", vec!["1.5e1", "150e-1"]),
+            ("0.5", vec!["5e-1", "50e-2"]),
+        ] {
+            for other in others {
+                assert_eq!(LiteralValue::parse(decimal), LiteralValue::parse(other));
+            }
+        }
+
+        // the coefficient must fit in 224 bits
+        let max = MAX_COEFFICIENT.to_string();
+        assert_eq!(
+            LiteralValue::parse(&format!("{}.{}", &max[..30], &max[30..])),
+            Ok(LiteralValue::Decimal {
+                coefficient: MAX_COEFFICIENT,
+                exponent: 30 - max.len() as i32
+            })
+        );
+        assert_eq!(
+            LiteralValue::parse(&format!("{}.0e3", max)),
+            Ok(LiteralValue::Decimal {
+                coefficient: MAX_COEFFICIENT,
+                exponent: 3
+            })
+        );
+        assert_eq!(
+            LiteralValue::parse(&format!("0.{}", MAX_COEFFICIENT + U256::from(1))),
+            Err(ErrorCode::OutOfRangePrecision)
+        );
+
+        // the value scaled by the exponent must fit in 256 bits
+        assert_eq!(LiteralValue::parse("1.1e77"), Ok(decimal(11, 76)));
+        assert_eq!(
+            LiteralValue::parse("1.2e77"),
+            Err(ErrorCode::OutOfRangeValue)
+        );
+        assert_eq!(
+            LiteralValue::parse("1e2147483647"),
+            Err(ErrorCode::OutOfRangeValue)
+        );
+        assert_eq!(
+            LiteralValue::parse("1e-2147483648"),
+            Ok(decimal(1, i32::MIN))
+        );
+        assert_eq!(
+            LiteralValue::parse("1e99999999999999999999"),
+            Err(ErrorCode::OutOfRangeValue)
+        );
+
+        assert_eq!(
+            LiteralValue::parse("\"some text\""),
+            Ok(LiteralValue::String("some text".to_owned()))
+        );
+        assert_eq!(
+            LiteralValue::parse("\"some text"),
+            Err(ErrorCode::UnexpectedStringLiteralEnd)
+        );
+        assert_eq!(
+            LiteralValue::parse(r#""a \"b\" \\ \n \x41\x7e""#),
+            Ok(LiteralValue::String("a \"b\" \\ \n A~".to_owned()))
+        );
+        assert_eq!(
+            LiteralValue::parse(r#""some \"text""#),
+            Ok(LiteralValue::String("some \"text".to_owned()))
+        );
+        assert_eq!(
+            LiteralValue::parse(r#""some \"text\""#),
+            Err(ErrorCode::UnexpectedStringLiteralEnd)
+        );
+        assert_eq!(
+            LiteralValue::parse(r#""some" text""#),
+            Err(ErrorCode::UnexpectedStringLiteralEnd)
+        );
+        for v in [r#""\t""#, r#""\x4""#, r#""\x+4""#, r#""\x4g""#, r#""\x80""#] {
+            assert_eq!(
+                LiteralValue::parse(v),
+                Err(ErrorCode::InvalidEscapeSequence)
+            );
+        }
+        let max = "a".repeat(MAX_STRING_BYTES);
+        assert_eq!(
+            LiteralValue::parse(&format!("\"{}\"", max)),
+            Ok(LiteralValue::String(max.clone()))
+        );
+        assert_eq!(
+            LiteralValue::parse(&format!("\"{}\\n\"", max)),
+            Err(ErrorCode::OutOfRangeStringLength)
+        );
+
+        assert_eq!(string_literal_end(r#""a\"b" c"#), Some(6));
+        assert_eq!(string_literal_end(r#""a\\" b""#), Some(5));
+        assert_eq!(string_literal_end(r#""a\" b"#), None);
+        assert_eq!(string_literal_end("a"), None);
+        assert_eq!(
+            LiteralValue::parse("[some-syntax 1]"),
+            Ok(LiteralValue::SubParser("some-syntax 1".to_owned()))
+        );
+        assert_eq!(
+            LiteralValue::parse("[some-syntax"),
+            Err(ErrorCode::UnexpectedSubParserEnd)
+        );
+        assert_eq!(LiteralValue::parse("abcd"), Err(ErrorCode::ExpectedLiteral));
+    }
+}
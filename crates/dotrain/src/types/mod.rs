@@ -1,7 +1,10 @@
 //! All data types of RainDocument/RainlangDocument parse tree and RegExp patterns used in parsing process
 
 pub mod ast;
+pub mod cst;
+pub mod literal;
 pub mod patterns;
+pub mod words;
 
 #[cfg(feature = "js-api")]
 mod impls;
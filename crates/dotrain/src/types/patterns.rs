@@ -30,9 +30,9 @@ pub static NUMERIC_PATTERN: Lazy<Regex> = Lazy::new(|| {
     .unwrap()
 });
 
-/// string literal pattern
+/// string literal pattern, quotes and backslashes inside it are escaped by a backslash
 pub static STRING_LITERAL_PATTERN: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"^\"[\s\S]*?\"$"#).unwrap());
+    Lazy::new(|| Regex::new(r#"^"(?:[^"\\]|\\[\s\S])*"$"#).unwrap());
 
 /// sub parser literal pattern
 pub static SUB_PARSER_LITERAL_PATTERN: Lazy<Regex> =
@@ -61,6 +61,13 @@ pub static E_PATTERN: Lazy<Regex> =
 /// Integer pattern
 pub static INT_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[0-9]+(\.[0-9]+)?$").unwrap());
 
+/// front matter YAML integer pattern
+pub static YAML_INT_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[-+]?[0-9]+$").unwrap());
+
+/// front matter YAML float pattern
+pub static YAML_FLOAT_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[-+]?(\.[0-9]+|[0-9]+(\.[0-9]*)?)([eE][-+]?[0-9]+)?$").unwrap());
+
 /// RainDocument Namespace pattern
 pub static NAMESPACE_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^(\.?[a-z][0-9a-z-]*)*\.?$").unwrap());
@@ -361,7 +368,13 @@ mod tests {
         }
 
         // invalids
-        for i in [r#"'ksdf iydsf'"#, r#"`ksdf iydsf`"#, r#"'ksdf iydsf""#] {
+        for i in [
+            r#"'ksdf iydsf'"#,
+            r#"`ksdf iydsf`"#,
+            r#"'ksdf iydsf""#,
+            r#""ksdf \" iydsf\""#,
+            r#""ksdf" iydsf""#,
+        ] {
             assert!(
                 !STRING_LITERAL_PATTERN.is_match(i),
                 "String '{}' considered valid.",
@@ -369,7 +382,11 @@ mod tests {
             );
         }
         // valids
-        for i in [r#""ksdf iydsf""#, r#""ksdf #$%&$% ()( {} __!@ ksjdhf 487""#] {
+        for i in [
+            r#""ksdf iydsf""#,
+            r#""ksdf #$%&$% ()( {} __!@ ksjdhf 487""#,
+            r#""ksdf \" iydsf \\""#,
+        ] {
             assert!(
                 STRING_LITERAL_PATTERN.is_match(i),
                 "String '{}' considered invalid.",
@@ -0,0 +1,168 @@
+//! Word descriptors that extend the authoring meta of words with the info needed for
+//! validating rainlang opcodes, such as their stack inputs and outputs and operand args
+
+use std::collections::HashMap;
+use alloy_primitives::U256;
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "js-api")]
+use tsify::Tsify;
+
+/// Kind of values that an operand arg accepts
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub enum OperandArgKind {
+    /// a literal or a reference to a literal binding
+    #[default]
+    Literal,
+    /// a quote of a binding or a reference to a quote binding
+    Quote,
+}
+
+/// Specification of an operand arg of a word
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct OperandArgSpec {
+    /// Name of the operand arg
+    pub name: String,
+    /// Description of the operand arg
+    #[serde(default)]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub description: String,
+    /// Kind of values the operand arg accepts
+    #[serde(default)]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub kind: OperandArgKind,
+    /// Minimum value of a literal operand arg
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub min: Option<u64>,
+    /// Maximum value of a literal operand arg
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub max: Option<u64>,
+    /// If the operand arg can be omitted, only the trailing operand args can be optional
+    #[serde(default)]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub optional: bool,
+}
+
+/// Specification of a word's stack arity and operand args
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct WordSpec {
+    /// Number of inputs of the word, or the minimum number of them if `max_inputs` is set
+    pub inputs: u8,
+    /// Maximum number of inputs of the word for words with variable inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub max_inputs: Option<u8>,
+    /// Number of outputs of the word
+    pub outputs: u8,
+    /// Operand args of the word in order, operand args are not checked if not set and
+    /// an empty list means the word takes no operand args
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "js-api", tsify(optional))]
+    pub operand_args: Option<Vec<OperandArgSpec>>,
+}
+
+/// Specifications of words keyed by the word, words that are not present are not validated
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "js-api", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct WordSpecs(pub HashMap<String, WordSpec>);
+
+impl WordSpec {
+    /// Checks if the given number of inputs is accepted by this word
+    pub fn accepts_inputs(&self, count: usize) -> bool {
+        count >= self.inputs as usize && count <= self.max_inputs.unwrap_or(self.inputs) as usize
+    }
+
+    /// Human readable range of the inputs of this word, such as `2` or `2 to 15`
+    pub fn inputs_range(&self) -> String {
+        match self.max_inputs {
+            Some(max) if max != self.inputs => format!("{} to {}", self.inputs, max),
+            _ => self.inputs.to_string(),
+        }
+    }
+
+    /// Signature of this word with the given name, i.e. the word followed by the names of its
+    /// operand args (optional ones end with `?`) and the range of its inputs and its outputs
+    pub fn signature(&self, word: &str) -> String {
+        let operand_args = self
+            .operand_args
+            .iter()
+            .flatten()
+            .map(|v| {
+                if v.optional {
+                    format!("{}?", v.name)
+                } else {
+                    v.name.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut signature = word.to_owned();
+        if !operand_args.is_empty() {
+            signature.push_str(&format!("<{}>", operand_args.join(" ")));
+        }
+        format!(
+            "{}\ninputs: {}, outputs: {}",
+            signature,
+            self.inputs_range(),
+            self.outputs
+        )
+    }
+
+    /// Checks if the given number of operand args is accepted by this word, always true
+    /// if the word has no operand args spec
+    pub fn accepts_operand_args(&self, count: usize) -> bool {
+        self.operand_args
+            .as_ref()
+            .is_none_or(|v| count >= v.iter().filter(|v| !v.optional).count() && count <= v.len())
+    }
+
+    /// Human readable range of the operand args of this word, such as `1` or `1 to 2`
+    pub fn operand_args_range(&self) -> String {
+        let Some(operand_args) = &self.operand_args else {
+            return String::new();
+        };
+        let min = operand_args.iter().filter(|v| !v.optional).count();
+        if min == operand_args.len() {
+            min.to_string()
+        } else {
+            format!("{} to {}", min, operand_args.len())
+        }
+    }
+}
+
+impl OperandArgSpec {
+    /// Checks if the given value is in the range of this operand arg
+    pub fn accepts_value(&self, value: U256) -> bool {
+        self.min.is_none_or(|v| value >= U256::from(v))
+            && self.max.is_none_or(|v| value <= U256::from(v))
+    }
+
+    /// Human readable range of the values of this operand arg, such as `0 to 255`
+    pub fn value_range(&self) -> String {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{} to {}", min, max),
+            (Some(min), None) => format!("at least {}", min),
+            (None, Some(max)) => format!("at most {}", max),
+            (None, None) => "any value".to_owned(),
+        }
+    }
+}
+
+impl WordSpecs {
+    /// Specification of the given word
+    pub fn get(&self, word: &str) -> Option<&WordSpec> {
+        self.0.get(word)
+    }
+
+    /// Checks if there is no word specification
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
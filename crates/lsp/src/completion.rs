@@ -1,5 +1,5 @@
 use regex::Regex;
-use super::OffsetAt;
+use super::{LineIndex, PositionEncoding};
 use once_cell::sync::Lazy;
 use alloy_primitives::hex;
 use std::collections::VecDeque;
@@ -27,8 +27,10 @@ pub fn get_completion(
     uri: &Url,
     position: Position,
     documentation_format: MarkupKind,
+    position_encoding: PositionEncoding,
 ) -> Option<Vec<CompletionItem>> {
-    let target_offset = rain_document.text().offset_at(&position);
+    let line_index = LineIndex::new(rain_document.text(), position_encoding);
+    let target_offset = line_index.offset_at(&position);
     if target_offset < rain_document.front_matter_offset() + 3 {
         return None;
     }
@@ -46,7 +48,7 @@ pub fn get_completion(
         {
             let pretext = rain_document
                 .text()
-                .get(import.position[0]..rain_document.text().offset_at(&position))?;
+                .get(import.position[0]..line_index.offset_at(&position))?;
             let chunks = exclusive_parse(pretext, &WS_PATTERN, 0, false);
             if let Some(configurations) = &import.configuration {
                 if configurations
@@ -156,10 +158,10 @@ pub fn get_completion(
             Some(Vec::from(result))
         } else {
             let pretext = rain_document.text().get(
-                rain_document.text().offset_at(&Position {
+                line_index.offset_at(&Position {
                     line: position.line,
                     character: 0,
-                })..rain_document.text().offset_at(&position),
+                })..line_index.offset_at(&position),
             )?;
             let mut prefix = get_prefix(pretext, &TRIGGERS);
             let is_quote = prefix.starts_with('\'');
@@ -167,7 +169,7 @@ pub fn get_completion(
                 prefix = prefix.split_at(1).1.to_owned();
             }
             if NAMESPACE_PATTERN.is_match(&prefix) {
-                let offset = rain_document.text().offset_at(&position);
+                let offset = line_index.offset_at(&position);
                 if let Some(namespace_node) = search_namespace(&prefix, rain_document.namespace()) {
                     result.extend(get_namespace_completions(
                         namespace_node,
@@ -188,7 +190,17 @@ pub fn get_completion(
                                 insert_text: Some(v.word.clone()),
                                 documentation: Some(Documentation::MarkupContent(MarkupContent {
                                     kind: documentation_format.clone(),
-                                    value: v.description.clone(),
+                                    value: match rain_document.word_specs().get(&v.word) {
+                                        Some(spec) if v.description.is_empty() => {
+                                            spec.signature(&v.word)
+                                        }
+                                        Some(spec) => format!(
+                                            "{}\n\n{}",
+                                            v.description,
+                                            spec.signature(&v.word)
+                                        ),
+                                        None => v.description.clone(),
+                                    },
                                 })),
                                 ..Default::default()
                             })
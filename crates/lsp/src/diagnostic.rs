@@ -1,9 +1,9 @@
-use super::PositionAt;
+use super::{LineIndex, PositionEncoding};
 use once_cell::sync::Lazy;
-use dotrain::RainDocument;
+use dotrain::{RainDocument, types::ast::Severity, error::ErrorCode};
 use regex::{Regex, Captures};
 use lsp_types::{
-    Diagnostic, Range, DiagnosticSeverity, DiagnosticRelatedInformation, Location, Url,
+    Diagnostic, DiagnosticSeverity, DiagnosticRelatedInformation, DiagnosticTag, Location, Url,
     NumberOrString,
 };
 
@@ -15,20 +15,19 @@ pub fn get_diagnostics(
     rain_document: &RainDocument,
     uri: &Url,
     related_information: bool,
+    position_encoding: PositionEncoding,
 ) -> Vec<Diagnostic> {
+    let line_index = LineIndex::new(rain_document.text(), position_encoding);
     let replacement =
         |caps: &Captures| -> String { " ".to_owned() + &caps[0].to_ascii_lowercase() };
     rain_document
         .all_problems()
         .iter()
         .map(|v| {
-            let range = Range::new(
-                rain_document.text().position_at(v.position[0]),
-                rain_document.text().position_at(v.position[1]),
-            );
-            Diagnostic::new(
+            let range = line_index.range_at(v.position);
+            let mut diagnostic = Diagnostic::new(
                 range,
-                Some(DiagnosticSeverity::ERROR),
+                Some(diagnostic_severity(v.severity)),
                 Some(NumberOrString::Number(v.code.to_i32())),
                 Some("rainlang".to_owned()),
                 if related_information {
@@ -40,18 +39,55 @@ pub fn get_diagnostics(
                     v.msg.clone()
                 },
                 if related_information {
-                    Some(vec![DiagnosticRelatedInformation {
+                    let mut info = vec![DiagnosticRelatedInformation {
                         message: v.msg.to_owned(),
                         location: Location {
                             uri: uri.clone(),
                             range,
                         },
-                    }])
+                    }];
+                    // locations of the problem inside imported dotrains, origins without a
+                    // valid uri are skipped as their ranges are not in this document's text
+                    for origin in v.origin.iter().flatten() {
+                        let Some(origin_uri) = origin.uri.as_ref().and_then(|v| Url::parse(v).ok())
+                        else {
+                            continue;
+                        };
+                        if let Some(dotrain) = rain_document.import_document(&origin.hash) {
+                            info.push(DiagnosticRelatedInformation {
+                                message: format!("imported from {}", origin.hash),
+                                location: Location {
+                                    uri: origin_uri,
+                                    range: LineIndex::new(dotrain.text(), position_encoding)
+                                        .range_at(origin.position),
+                                },
+                            });
+                        }
+                    }
+                    Some(info)
                 } else {
                     None
                 },
                 None,
-            )
+            );
+            // dead code is faded out by clients
+            if matches!(
+                v.code,
+                ErrorCode::UnusedBinding | ErrorCode::UnusedImport | ErrorCode::UnusedAlias
+            ) {
+                diagnostic.tags = Some(vec![DiagnosticTag::UNNECESSARY]);
+            }
+            diagnostic
         })
         .collect()
 }
+
+/// Converts the given problem severity to LSP diagnostic severity
+fn diagnostic_severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::Hint => DiagnosticSeverity::HINT,
+    }
+}
@@ -0,0 +1,64 @@
+use super::{LineIndex, PositionEncoding};
+use lsp_types::TextEdit;
+use dotrain::{format_dotrain, RainDocument};
+
+/// Provides formatting edits for the given RainDocument, the changed part of the text is
+/// replaced with a single edit so the unchanged leading and trailing parts are left untouched
+pub fn get_formatting(
+    rain_document: &RainDocument,
+    position_encoding: PositionEncoding,
+) -> Vec<TextEdit> {
+    let text = rain_document.text();
+    let formatted = format_dotrain(text);
+    if formatted == text {
+        return vec![];
+    }
+    let mut prefix = text
+        .bytes()
+        .zip(formatted.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !text.is_char_boundary(prefix) || !formatted.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+    let max_suffix = text.len().min(formatted.len()) - prefix;
+    let mut suffix = text
+        .bytes()
+        .rev()
+        .zip(formatted.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !text.is_char_boundary(text.len() - suffix)
+        || !formatted.is_char_boundary(formatted.len() - suffix)
+    {
+        suffix -= 1;
+    }
+    vec![TextEdit {
+        range: LineIndex::new(text, position_encoding).range_at([prefix, text.len() - suffix]),
+        new_text: formatted[prefix..formatted.len() - suffix].to_owned(),
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Position, Range};
+
+    #[test]
+    fn test_get_formatting() {
+        let text = "---\n#a   4\n#b 5\n";
+        let rain_document = RainDocument::create(text.to_owned(), None, None, None);
+        let edits = get_formatting(&rain_document, PositionEncoding::Utf16);
+        assert_eq!(
+            edits,
+            vec![TextEdit {
+                range: Range::new(Position::new(1, 3), Position::new(1, 6)),
+                new_text: "4\n".to_owned(),
+            }]
+        );
+
+        let rain_document = RainDocument::create(format_dotrain(text), None, None, None);
+        assert!(get_formatting(&rain_document, PositionEncoding::Utf16).is_empty());
+    }
+}
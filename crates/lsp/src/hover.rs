@@ -1,4 +1,4 @@
-use super::{OffsetAt, PositionAt};
+use super::{LineIndex, PositionEncoding};
 use dotrain::{
     RainDocument,
     types::{ast::*, patterns::*},
@@ -11,8 +11,10 @@ pub fn get_hover(
     rain_document: &RainDocument,
     position: Position,
     content_type: MarkupKind,
+    position_encoding: PositionEncoding,
 ) -> Option<Hover> {
-    let target_offset = rain_document.text().offset_at(&position);
+    let line_index = LineIndex::new(rain_document.text(), position_encoding);
+    let target_offset = line_index.offset_at(&position);
     if let Some(import) = rain_document
         .imports()
         .iter()
@@ -29,8 +31,8 @@ pub fn get_hover(
                     },
                 }),
                 range: Some(Range::new(
-                    rain_document.text().position_at(import.position[0]),
-                    rain_document.text().position_at(import.position[1]),
+                    line_index.position_at(import.position[0]),
+                    line_index.position_at(import.position[1]),
                 )),
             })
         } else {
@@ -58,8 +60,8 @@ pub fn get_hover(
                         .to_owned(),
                     }),
                     range: Some(Range::new(
-                        rain_document.text().position_at(binding.name_position[0]),
-                        rain_document.text().position_at(binding.name_position[1]),
+                        line_index.position_at(binding.name_position[0]),
+                        line_index.position_at(binding.name_position[1]),
                     )),
                 });
             } else if binding.content_position[0] <= target_offset
@@ -88,7 +90,7 @@ pub fn get_hover(
                             binding.content_position[0],
                             target_offset - binding.content_position[0],
                             content_type,
-                            rain_document.text(),
+                            &line_index,
                         );
                     }
                     BindingItem::Literal(_) => {
@@ -98,12 +100,8 @@ pub fn get_hover(
                                 value: "literal value".to_owned(),
                             }),
                             range: Some(Range::new(
-                                rain_document
-                                    .text()
-                                    .position_at(binding.content_position[0]),
-                                rain_document
-                                    .text()
-                                    .position_at(binding.content_position[1]),
+                                line_index.position_at(binding.content_position[0]),
+                                line_index.position_at(binding.content_position[1]),
                             )),
                         })
                     }
@@ -114,12 +112,8 @@ pub fn get_hover(
                                 value: "elision msg".to_owned(),
                             }),
                             range: Some(Range::new(
-                                rain_document
-                                    .text()
-                                    .position_at(binding.content_position[0]),
-                                rain_document
-                                    .text()
-                                    .position_at(binding.content_position[1]),
+                                line_index.position_at(binding.content_position[0]),
+                                line_index.position_at(binding.content_position[1]),
                             )),
                         })
                     }
@@ -130,12 +124,8 @@ pub fn get_hover(
                                 value: "quote binding".to_owned(),
                             }),
                             range: Some(Range::new(
-                                rain_document
-                                    .text()
-                                    .position_at(binding.content_position[0]),
-                                rain_document
-                                    .text()
-                                    .position_at(binding.content_position[1]),
+                                line_index.position_at(binding.content_position[0]),
+                                line_index.position_at(binding.content_position[1]),
                             )),
                         })
                     }
@@ -152,7 +142,7 @@ fn search(
     offset: usize,
     target_offset: usize,
     kind: MarkupKind,
-    text: &str,
+    line_index: &LineIndex,
 ) -> Option<Hover> {
     for node in nodes {
         let node_pos = node.position();
@@ -166,7 +156,7 @@ fn search(
                             offset,
                             target_offset,
                             kind,
-                            text,
+                            line_index,
                         );
                     } else if let Some(og) = &op.operand_args {
                         if og.position[0] < target_offset && og.position[1] > target_offset {
@@ -198,8 +188,8 @@ fn search(
                                             value,
                                         }),
                                         range: Some(Range::new(
-                                            text.position_at(arg.position[0] + offset),
-                                            text.position_at(arg.position[1] + offset),
+                                            line_index.position_at(arg.position[0] + offset),
+                                            line_index.position_at(arg.position[1] + offset),
                                         )),
                                     });
                                 }
@@ -209,11 +199,11 @@ fn search(
                             return Some(Hover {
                                 contents: HoverContents::Markup(MarkupContent {
                                     kind,
-                                    value: op.opcode.description.clone(),
+                                    value: opcode_documentation(rain_document, op),
                                 }),
                                 range: Some(Range::new(
-                                    text.position_at(op.opcode.position[0] + offset),
-                                    text.position_at(op.parens[1] + offset),
+                                    line_index.position_at(op.opcode.position[0] + offset),
+                                    line_index.position_at(op.parens[1] + offset),
                                 )),
                             });
                         }
@@ -221,11 +211,11 @@ fn search(
                         return Some(Hover {
                             contents: HoverContents::Markup(MarkupContent {
                                 kind,
-                                value: op.opcode.description.clone(),
+                                value: opcode_documentation(rain_document, op),
                             }),
                             range: Some(Range::new(
-                                text.position_at(op.opcode.position[0] + offset),
-                                text.position_at(op.parens[1] + offset),
+                                line_index.position_at(op.opcode.position[0] + offset),
+                                line_index.position_at(op.parens[1] + offset),
                             )),
                         });
                     }
@@ -241,8 +231,8 @@ fn search(
                             kind,
                         }),
                         range: Some(Range::new(
-                            text.position_at(literal.position[0] + offset),
-                            text.position_at(literal.position[1] + offset),
+                            line_index.position_at(literal.position[0] + offset),
+                            line_index.position_at(literal.position[1] + offset),
                         )),
                     });
                 }
@@ -275,8 +265,8 @@ fn search(
                     return Some(Hover {
                         contents: HoverContents::Markup(MarkupContent { kind, value }),
                         range: Some(Range::new(
-                            text.position_at(alias.position[0] + offset),
-                            text.position_at(alias.position[1] + offset),
+                            line_index.position_at(alias.position[0] + offset),
+                            line_index.position_at(alias.position[1] + offset),
                         )),
                     });
                 }
@@ -286,6 +276,19 @@ fn search(
     None
 }
 
+/// Description of the given opcode followed by the signature of its word if it has a word spec
+fn opcode_documentation(rain_document: &RainDocument, op: &Opcode) -> String {
+    match rain_document.word_specs().get(&op.opcode.name) {
+        Some(spec) if op.opcode.description.is_empty() => spec.signature(&op.opcode.name),
+        Some(spec) => format!(
+            "{}\n\n{}",
+            op.opcode.description,
+            spec.signature(&op.opcode.name)
+        ),
+        None => op.opcode.description.clone(),
+    }
+}
+
 fn search_binding_ref<'a>(query: &str, namespace: &'a Namespace) -> Option<&'a Binding> {
     let mut segments: &[ParsedItem] = &exclusive_parse(query, &NAMESPACE_SEGMENT_PATTERN, 0, true);
     if query.starts_with('.') {
@@ -1,8 +1,10 @@
 use wasm_bindgen::prelude::*;
 use lsp_types::{MarkupKind as MK, Position as Pos, TextDocumentItem as TDI, Url};
 use serde_wasm_bindgen::{to_value as to_js_value, from_value as from_js_value};
-use dotrain::{js_api::MetaStore, RainDocument, Rebind};
-use super::{RainLanguageServices, LanguageServiceParams};
+use dotrain::{
+    js_api::MetaStore, RainDocument, Rebind, FrontMatterSchema, LintConfig, types::words::WordSpecs,
+};
+use super::{RainLanguageServices, LanguageServiceParams, PositionEncoding};
 
 #[wasm_bindgen]
 extern "C" {
@@ -34,12 +36,20 @@ extern "C" {
     /// it can be deserialized to rust [mod@lsp_types::SemanticTokensPartialResult] using `serde-wasm-bindgen`
     #[wasm_bindgen(typescript_type = "SemanticTokensPartialResult")]
     pub type SemanticTokensPartialResult;
+    /// A wrapped JsValue representing typescript LSP TextEdit array in rust,
+    /// it can be deserialized to rust [mod@lsp_types::TextEdit] vec using `serde-wasm-bindgen`
+    #[wasm_bindgen(typescript_type = "TextEdit[]")]
+    pub type TextEditArray;
+    /// A wrapped JsValue representing typescript LSP PositionEncodingKind type in rust,
+    /// it can be deserialized to rust [mod@lsp_types::PositionEncodingKind] using `serde-wasm-bindgen`
+    #[wasm_bindgen(typescript_type = "PositionEncodingKind")]
+    pub type PositionEncodingKind;
 }
 
 #[wasm_bindgen(typescript_custom_section)]
 const LSP_TS_IMPORTS: &'static str = r#"
-import { SemanticTokensPartialResult } from "vscode-languageserver-protocol";
-import { Hover, Position, MarkupKind, Diagnostic, CompletionItem, TextDocumentItem } from "vscode-languageserver-types";
+import { SemanticTokensPartialResult, PositionEncodingKind } from "vscode-languageserver-protocol";
+import { Hover, Position, MarkupKind, Diagnostic, CompletionItem, TextDocumentItem, TextEdit } from "vscode-languageserver-types";
 "#;
 
 #[wasm_bindgen]
@@ -50,11 +60,53 @@ impl RainLanguageServices {
         self.meta_store.clone().into()
     }
 
-    /// Instantiates with the given MetaStore
+    /// The position encoding of LSP positions of this RainLanguageServices instance
+    #[wasm_bindgen(getter, js_name = "positionEncoding")]
+    pub fn js_position_encoding(&self) -> PositionEncodingKind {
+        PositionEncodingKind {
+            obj: to_js_value(&self.position_encoding.kind()).unwrap_or(JsValue::NULL),
+        }
+    }
+
+    /// The front matter schema of this RainLanguageServices instance
+    #[wasm_bindgen(getter, js_name = "frontMatterSchema")]
+    pub fn js_front_matter_schema(&self) -> Option<FrontMatterSchema> {
+        self.front_matter_schema.clone()
+    }
+
+    /// The lint config of this RainLanguageServices instance
+    #[wasm_bindgen(getter, js_name = "lintConfig")]
+    pub fn js_lint_config(&self) -> LintConfig {
+        self.lint_config.clone()
+    }
+
+    /// The word specs of this RainLanguageServices instance
+    #[wasm_bindgen(getter, js_name = "wordSpecs")]
+    pub fn js_word_specs(&self) -> WordSpecs {
+        self.word_specs.clone()
+    }
+
+    /// Instantiates with the given MetaStore, the position encoding negotiated with the
+    /// client (defaults to UTF-16 if not provided), the schema that the front matter of
+    /// documents are validated against, the levels of the lints run over documents and
+    /// the specs of words that the opcodes of documents are checked against
     #[wasm_bindgen(constructor)]
-    pub fn js_new(meta_store: &MetaStore) -> RainLanguageServices {
+    pub fn js_new(
+        meta_store: &MetaStore,
+        position_encoding: Option<PositionEncodingKind>,
+        front_matter_schema: Option<FrontMatterSchema>,
+        lint_config: Option<LintConfig>,
+        word_specs: Option<WordSpecs>,
+    ) -> RainLanguageServices {
         RainLanguageServices::new(&LanguageServiceParams {
             meta_store: Some(meta_store.into()),
+            position_encoding: position_encoding
+                .and_then(|v| from_js_value::<lsp_types::PositionEncodingKind>(v.obj).ok())
+                .and_then(|v| PositionEncoding::from_kind(&v))
+                .unwrap_or_default(),
+            front_matter_schema,
+            lint_config: lint_config.unwrap_or_default(),
+            word_specs: word_specs.unwrap_or_default(),
         })
     }
 
@@ -264,4 +316,21 @@ impl RainLanguageServices {
             .unwrap_or(JsValue::NULL),
         }
     }
+
+    /// Provides formatting edits for the given text document
+    #[wasm_bindgen(js_name = "doFormat")]
+    pub fn js_do_format(&self, text_document: TextDocumentItem) -> TextEditArray {
+        let tdi = from_js_value::<TDI>(text_document.obj).unwrap_throw();
+        TextEditArray {
+            obj: to_js_value(&self.do_format(&tdi)).unwrap_or(JsValue::NULL),
+        }
+    }
+
+    /// Provides formatting edits for the given RainDocument
+    #[wasm_bindgen(js_name = "doFormatRainDocument")]
+    pub fn js_do_format_rain_document(&self, rain_document: &RainDocument) -> TextEditArray {
+        TextEditArray {
+            obj: to_js_value(&self.do_format_rain_document(rain_document)).unwrap_or(JsValue::NULL),
+        }
+    }
 }
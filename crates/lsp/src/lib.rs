@@ -8,10 +8,10 @@
 //! - Dotrain vscode extension can be found [here](https://marketplace.visualstudio.com/items?itemName=rainprotocol.rainlang-vscode).
 
 use std::sync::{Arc, RwLock};
-use dotrain::{RainDocument, Store, Rebind};
+use dotrain::{RainDocument, Store, Rebind, FrontMatterSchema, LintConfig, types::words::WordSpecs};
 use lsp_types::{
     Hover, Position, Diagnostic, MarkupKind, CompletionItem, TextDocumentItem,
-    SemanticTokensPartialResult, Url,
+    SemanticTokensPartialResult, Url, TextEdit,
 };
 
 #[cfg(feature = "js-api")]
@@ -23,20 +23,32 @@ pub use hover::get_hover;
 pub use completion::get_completion;
 pub use diagnostic::get_diagnostics;
 pub use semantic_token::get_semantic_token;
+pub use formatting::get_formatting;
+pub use line_index::{LineIndex, PositionEncoding};
 
 mod hover;
 mod completion;
 mod diagnostic;
 mod semantic_token;
+mod formatting;
+mod line_index;
 
 #[cfg(feature = "js-api")]
 pub mod js_api;
 
 /// Parameters for initiating Language Services
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct LanguageServiceParams {
     /// The meta Store (CAS) instance used for all parsings of the RainLanguageServices
     pub meta_store: Option<Arc<RwLock<Store>>>,
+    /// The position encoding negotiated with the client, see [PositionEncoding::negotiate()]
+    pub position_encoding: PositionEncoding,
+    /// The schema that the front matter of the parsed documents is validated against
+    pub front_matter_schema: Option<FrontMatterSchema>,
+    /// The levels of the lints run over the parsed documents
+    pub lint_config: LintConfig,
+    /// The specs of words that the opcodes of the parsed documents are checked against
+    pub word_specs: WordSpecs,
 }
 
 #[cfg_attr(
@@ -46,9 +58,10 @@ for a given TextDocumentItem or a RainDocument. Each instance is linked to a sha
 [Store] instance `Arc<RwLock<Store>>` that holds all the required metadata/functionalities that 
 are required during parsing a text.
 
-Position encodings provided by the client are irrevelant as RainDocument/Rainlang supports
-only ASCII characters (parsing will stop at very first encountered non-ASCII character), so any
-position encodings will result in the same LSP provided Position value which is 1 for each char.
+RainDocument/Rainlang offsets are in bytes while LSP Position characters are in the position
+encoding negotiated with the client (UTF-16 by default), non-ASCII characters can appear in front
+matter, comments and string literals and are converted accordingly, see [PositionEncoding] and
+[LineIndex].
 
 ## Example
 
@@ -57,7 +70,8 @@ use std::sync::{Arc, RwLock};
 use dotrain_lsp::{
     RainLanguageServices, 
     LanguageServiceParams, 
-    dotrain::Store, 
+    PositionEncoding,
+    dotrain::{Store, LintConfig, types::words::WordSpecs},
     lsp_types::{TextDocumentItem, MarkupKind, Position, Url}
 };
 
@@ -66,7 +80,11 @@ let meta_store = Arc::new(RwLock::new(Store::default()));
 
 // create instatiation params
 let params = LanguageServiceParams {
-    meta_store: Some(meta_store)
+    meta_store: Some(meta_store),
+    position_encoding: PositionEncoding::Utf16,
+    front_matter_schema: None,
+    lint_config: LintConfig::default(),
+    word_specs: WordSpecs::default(),
 };
 
 // create a new instane with a shared locked Store that is used for all
@@ -103,9 +121,9 @@ let hover = lang_services.do_hover(&text_document, position, content_format, Non
  MetaStore instance that holds all the required metadata/functionalities that are required during 
  parsing a text.
 
- Position encodings provided by the client are irrevelant as RainDocument/Rainlang supports
- only ASCII characters (parsing will stop at very first encountered non-ASCII character), so any
- position encodings will result in the same LSP provided Position value which is 1 for each char.
+ RainDocument/Rainlang offsets are in bytes while LSP Position characters are in the position
+ encoding negotiated with the client (UTF-16 by default), non-ASCII characters can appear in front
+ matter, comments and string literals and are converted accordingly.
  
  @example
  ```javascript
@@ -134,12 +152,22 @@ let hover = lang_services.do_hover(&text_document, position, content_format, Non
 #[cfg_attr(feature = "js-api", wasm_bindgen)]
 pub struct RainLanguageServices {
     pub(crate) meta_store: Arc<RwLock<Store>>,
+    pub(crate) position_encoding: PositionEncoding,
+    pub(crate) front_matter_schema: Option<FrontMatterSchema>,
+    pub(crate) lint_config: LintConfig,
+    pub(crate) word_specs: WordSpecs,
 }
 
 impl Default for RainLanguageServices {
     fn default() -> Self {
         let meta_store = Arc::new(RwLock::new(Store::default()));
-        RainLanguageServices { meta_store }
+        RainLanguageServices {
+            meta_store,
+            position_encoding: PositionEncoding::default(),
+            front_matter_schema: None,
+            lint_config: LintConfig::default(),
+            word_specs: WordSpecs::default(),
+        }
     }
 }
 
@@ -148,6 +176,22 @@ impl RainLanguageServices {
     pub fn meta_store(&self) -> Arc<RwLock<Store>> {
         self.meta_store.clone()
     }
+    /// The position encoding of LSP positions of this RainLanguageServices instance
+    pub fn position_encoding(&self) -> PositionEncoding {
+        self.position_encoding
+    }
+    /// The front matter schema of this RainLanguageServices instance
+    pub fn front_matter_schema(&self) -> &Option<FrontMatterSchema> {
+        &self.front_matter_schema
+    }
+    /// The lint config of this RainLanguageServices instance
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+    /// The word specs of this RainLanguageServices instance
+    pub fn word_specs(&self) -> &WordSpecs {
+        &self.word_specs
+    }
     /// Instantiates from the given params
     pub fn new(language_params: &LanguageServiceParams) -> RainLanguageServices {
         RainLanguageServices {
@@ -155,6 +199,10 @@ impl RainLanguageServices {
                 .meta_store
                 .as_ref()
                 .map_or(Arc::new(RwLock::new(Store::default())), |s| s.clone()),
+            position_encoding: language_params.position_encoding,
+            front_matter_schema: language_params.front_matter_schema.clone(),
+            lint_config: language_params.lint_config.clone(),
+            word_specs: language_params.word_specs.clone(),
         }
     }
 
@@ -164,12 +212,16 @@ impl RainLanguageServices {
         text_document: &TextDocumentItem,
         rebinds: Option<Vec<Rebind>>,
     ) -> RainDocument {
-        RainDocument::create(
+        let mut rain_document = RainDocument::create(
             text_document.text.clone(),
             Some(self.meta_store.clone()),
             None,
             rebinds,
-        )
+        );
+        rain_document.set_front_matter_schema(self.front_matter_schema.clone());
+        rain_document.set_lint_config(self.lint_config.clone());
+        rain_document.set_word_specs(self.word_specs.clone());
+        rain_document
     }
     /// Instantiates a RainDocument with remote meta search enabled when parsing from the given TextDocumentItem
     pub async fn new_rain_document_async(
@@ -177,13 +229,17 @@ impl RainLanguageServices {
         text_document: &TextDocumentItem,
         rebinds: Option<Vec<Rebind>>,
     ) -> RainDocument {
-        RainDocument::create_async(
+        let mut rain_document = RainDocument::create_async(
             text_document.text.clone(),
             Some(self.meta_store.clone()),
             None,
             rebinds,
         )
-        .await
+        .await;
+        rain_document.set_front_matter_schema(self.front_matter_schema.clone());
+        rain_document.set_lint_config(self.lint_config.clone());
+        rain_document.set_word_specs(self.word_specs.clone());
+        rain_document
     }
 
     /// Validates the document with remote meta search disabled when parsing and reports LSP diagnostics
@@ -193,13 +249,13 @@ impl RainLanguageServices {
         related_information: bool,
         rebinds: Option<Vec<Rebind>>,
     ) -> Vec<Diagnostic> {
-        let rain_document = RainDocument::create(
-            text_document.text.clone(),
-            Some(self.meta_store.clone()),
-            None,
-            rebinds,
-        );
-        diagnostic::get_diagnostics(&rain_document, &text_document.uri, related_information)
+        let rain_document = self.new_rain_document(text_document, rebinds);
+        diagnostic::get_diagnostics(
+            &rain_document,
+            &text_document.uri,
+            related_information,
+            self.position_encoding,
+        )
     }
     /// Validates the document with remote meta search enabled when parsing and reports LSP diagnostics
     pub async fn do_validate_async(
@@ -208,14 +264,13 @@ impl RainLanguageServices {
         related_information: bool,
         rebinds: Option<Vec<Rebind>>,
     ) -> Vec<Diagnostic> {
-        let rain_document = RainDocument::create_async(
-            text_document.text.clone(),
-            Some(self.meta_store.clone()),
-            None,
-            rebinds,
+        let rain_document = self.new_rain_document_async(text_document, rebinds).await;
+        diagnostic::get_diagnostics(
+            &rain_document,
+            &text_document.uri,
+            related_information,
+            self.position_encoding,
         )
-        .await;
-        diagnostic::get_diagnostics(&rain_document, &text_document.uri, related_information)
     }
     /// Reports LSP diagnostics from RainDocument's all problems
     pub fn do_validate_rain_document(
@@ -224,7 +279,12 @@ impl RainLanguageServices {
         uri: &Url,
         related_information: bool,
     ) -> Vec<Diagnostic> {
-        diagnostic::get_diagnostics(rain_document, uri, related_information)
+        diagnostic::get_diagnostics(
+            rain_document,
+            uri,
+            related_information,
+            self.position_encoding,
+        )
     }
 
     /// Provides completion items at the given position
@@ -235,17 +295,13 @@ impl RainLanguageServices {
         documentation_format: Option<MarkupKind>,
         rebinds: Option<Vec<Rebind>>,
     ) -> Option<Vec<CompletionItem>> {
-        let rain_document = RainDocument::create(
-            text_document.text.clone(),
-            Some(self.meta_store.clone()),
-            None,
-            rebinds,
-        );
+        let rain_document = self.new_rain_document(text_document, rebinds);
         completion::get_completion(
             &rain_document,
             &text_document.uri,
             position,
             documentation_format.unwrap_or(MarkupKind::PlainText),
+            self.position_encoding,
         )
     }
     /// Provides completion items at the given position
@@ -261,6 +317,7 @@ impl RainLanguageServices {
             uri,
             position,
             documentation_format.unwrap_or(MarkupKind::PlainText),
+            self.position_encoding,
         )
     }
 
@@ -272,16 +329,12 @@ impl RainLanguageServices {
         content_format: Option<MarkupKind>,
         rebinds: Option<Vec<Rebind>>,
     ) -> Option<Hover> {
-        let rain_document = RainDocument::create(
-            text_document.text.clone(),
-            Some(self.meta_store.clone()),
-            None,
-            rebinds,
-        );
+        let rain_document = self.new_rain_document(text_document, rebinds);
         hover::get_hover(
             &rain_document,
             position,
             content_format.unwrap_or(MarkupKind::PlainText),
+            self.position_encoding,
         )
     }
     /// Provides hover for a RainDocument fragment at the given position
@@ -295,6 +348,7 @@ impl RainLanguageServices {
             rain_document,
             position,
             content_format.unwrap_or(MarkupKind::PlainText),
+            self.position_encoding,
         )
     }
 
@@ -316,6 +370,7 @@ impl RainLanguageServices {
             &rain_document,
             semantic_token_types_index,
             semantic_token_modifiers_len,
+            self.position_encoding,
         )
     }
     /// Provides semantic tokens for RainDocument's elided fragments
@@ -329,135 +384,67 @@ impl RainLanguageServices {
             rain_document,
             semantic_token_types_index,
             semantic_token_modifiers_len,
+            self.position_encoding,
         )
     }
+
+    /// Provides formatting edits for the given text document
+    pub fn do_format(&self, text_document: &TextDocumentItem) -> Vec<TextEdit> {
+        let rain_document = self.new_rain_document(text_document, None);
+        get_formatting(&rain_document, self.position_encoding)
+    }
+    /// Provides formatting edits for the given RainDocument
+    pub fn do_format_rain_document(&self, rain_document: &RainDocument) -> Vec<TextEdit> {
+        get_formatting(rain_document, self.position_encoding)
+    }
 }
 
-/// Trait for converting offset to lsp position (implemented for `&str` and `String`)
+/// Trait for converting offset to lsp position (implemented for `&str` and `String`), characters
+/// are in UTF-16 code units, see [LineIndex] for other position encodings
 pub trait PositionAt {
     fn position_at(&self, offset: usize) -> Position;
 }
 
-/// Trait for converting lsp position to offset (implemented for `&str` and `String`)
+/// Trait for converting lsp position to offset (implemented for `&str` and `String`), characters
+/// are in UTF-16 code units, see [LineIndex] for other position encodings
 pub trait OffsetAt {
     fn offset_at(&self, position: &Position) -> usize;
 }
 
 impl PositionAt for &str {
     fn position_at(&self, offset: usize) -> Position {
-        let effective_offset = 0.max(offset.min(self.len()));
-        let mut line_offsets = vec![];
-        let mut acc = 0;
-        self.split_inclusive('\n').for_each(|v| {
-            line_offsets.push(acc);
-            acc += v.len();
-        });
-        let mut low = 0;
-        let mut high = line_offsets.len();
-        if high == 0 {
-            return Position {
-                line: 0,
-                character: effective_offset as u32,
-            };
-        }
-        while low < high {
-            let mid = (low + high) / 2;
-            if line_offsets[mid] > effective_offset {
-                high = mid;
-            } else {
-                low = mid + 1;
-            }
-        }
-        // low is the least x for which the line offset is larger than the current offset
-        // or array.length if no line offset is larger than the current offset
-        let line = low - 1;
-        Position {
-            line: line as u32,
-            character: (effective_offset - line_offsets[line]) as u32,
-        }
+        LineIndex::new(self, PositionEncoding::Utf16).position_at(offset)
     }
 }
 
 impl OffsetAt for &str {
     fn offset_at(&self, position: &Position) -> usize {
-        let mut line_offsets = vec![];
-        let mut acc = 0;
-        self.split_inclusive('\n').for_each(|v| {
-            line_offsets.push(acc);
-            acc += v.len();
-        });
-        if position.line >= line_offsets.len() as u32 {
-            return self.len();
-        }
-        let line_offset = line_offsets[position.line as usize];
-        let next_line_offset = if position.line + 1 < line_offsets.len() as u32 {
-            line_offsets[position.line as usize + 1]
-        } else {
-            self.len()
-        };
-        line_offset.max((line_offset + position.character as usize).min(next_line_offset))
+        LineIndex::new(self, PositionEncoding::Utf16).offset_at(position)
     }
 }
 
 impl PositionAt for String {
     fn position_at(&self, offset: usize) -> Position {
-        let effective_offset = 0.max(offset.min(self.len()));
-        let mut line_offsets = vec![];
-        let mut acc = 0;
-        self.split_inclusive('\n').for_each(|v| {
-            line_offsets.push(acc);
-            acc += v.len();
-        });
-        let mut low = 0;
-        let mut high = line_offsets.len();
-        if high == 0 {
-            return Position {
-                line: 0,
-                character: effective_offset as u32,
-            };
-        }
-        while low < high {
-            let mid = (low + high) / 2;
-            if line_offsets[mid] > effective_offset {
-                high = mid;
-            } else {
-                low = mid + 1;
-            }
-        }
-        // low is the least x for which the line offset is larger than the current offset
-        // or array.length if no line offset is larger than the current offset
-        let line = low - 1;
-        Position {
-            line: line as u32,
-            character: (effective_offset - line_offsets[line]) as u32,
-        }
+        self.as_str().position_at(offset)
     }
 }
 
 impl OffsetAt for String {
     fn offset_at(&self, position: &Position) -> usize {
-        let mut line_offsets = vec![];
-        let mut acc = 0;
-        self.split_inclusive('\n').for_each(|v| {
-            line_offsets.push(acc);
-            acc += v.len();
-        });
-        if position.line >= line_offsets.len() as u32 {
-            return self.len();
-        }
-        let line_offset = line_offsets[position.line as usize];
-        let next_line_offset = if position.line + 1 < line_offsets.len() as u32 {
-            line_offsets[position.line as usize + 1]
-        } else {
-            self.len()
-        };
-        line_offset.max((line_offset + position.character as usize).min(next_line_offset))
+        self.as_str().offset_at(position)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use dotrain::{
+        FrontMatterType, FrontMatterTypes, Lint, LintLevel,
+        error::ErrorCode,
+        types::words::{OperandArgKind, OperandArgSpec, WordSpec},
+    };
+    use lsp_types::{DiagnosticSeverity, DiagnosticTag, NumberOrString};
 
     #[test]
     fn test_position_at() {
@@ -500,4 +487,203 @@ mod tests {
         let expected_offset2 = 28;
         assert_eq!(offset2, expected_offset2);
     }
+
+    #[test]
+    fn test_unicode_position_and_offset() {
+        // "é" is 2 bytes and 1 UTF-16 code unit, "𝄞" is 4 bytes and 2 UTF-16 code units
+        let text = "---\n/* é𝄞 */ #a 1\n";
+        let offset = text.find("#a").unwrap();
+        assert_eq!(offset, 17);
+
+        let position = text.position_at(offset);
+        assert_eq!(
+            position,
+            Position {
+                line: 1,
+                character: 10,
+            }
+        );
+        assert_eq!(text.offset_at(&position), offset);
+        assert_eq!(text.to_owned().offset_at(&position), offset);
+
+        // offsets inside of a char are floored to the char start
+        assert_eq!(
+            text.position_at(8),
+            Position {
+                line: 1,
+                character: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_position_encoding() {
+        // an undefined identifier after a comment with a 4 bytes (2 UTF-16 code units) char
+        let text_document = TextDocumentItem {
+            uri: Url::parse("file:///example.rain").unwrap(),
+            text: "---\n#exp /* 𝄞 */ _: a;".to_owned(),
+            version: 0,
+            language_id: "rainlang".to_owned(),
+        };
+        for (position_encoding, character) in [
+            (PositionEncoding::Utf8, 19),
+            (PositionEncoding::Utf16, 17),
+            (PositionEncoding::Utf32, 16),
+        ] {
+            let lang_services = RainLanguageServices::new(&LanguageServiceParams {
+                meta_store: None,
+                position_encoding,
+                ..Default::default()
+            });
+            let diagnostics = lang_services.do_validate(&text_document, false, None);
+            assert_eq!(
+                diagnostics[0].range,
+                lsp_types::Range::new(Position::new(1, character), Position::new(1, character + 1))
+            );
+        }
+    }
+
+    #[test]
+    fn test_front_matter_schema() {
+        let text_document = TextDocumentItem {
+            uri: Url::parse("file:///example.rain").unwrap(),
+            text: "name: 1
+---
+#exp 1"
+                .to_owned(),
+            version: 0,
+            language_id: "rainlang".to_owned(),
+        };
+        let lang_services = RainLanguageServices::new(&LanguageServiceParams {
+            front_matter_schema: Some(FrontMatterSchema {
+                properties: Some(HashMap::from([(
+                    "name".to_owned(),
+                    FrontMatterSchema {
+                        types: Some(FrontMatterTypes::Single(FrontMatterType::String)),
+                        ..Default::default()
+                    },
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let diagnostics = lang_services.do_validate(&text_document, false, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "invalid front matter value at /name: expected string, found number"
+        );
+        assert_eq!(
+            diagnostics[0].range,
+            lsp_types::Range::new(Position::new(0, 6), Position::new(0, 7))
+        );
+        assert!(RainLanguageServices::default()
+            .do_validate(&text_document, false, None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_lint_diagnostics() {
+        let text_document = TextDocumentItem {
+            uri: Url::parse("file:///example.rain").unwrap(),
+            text: "---\n/* @entrypoint */ #main _: 1;\n#unused 2".to_owned(),
+            version: 0,
+            language_id: "rainlang".to_owned(),
+        };
+        let diagnostics = RainLanguageServices::default().do_validate(&text_document, false, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unused binding: unused");
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].tags, Some(vec![DiagnosticTag::UNNECESSARY]));
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::Number(ErrorCode::UnusedBinding.to_i32()))
+        );
+
+        let lang_services = RainLanguageServices::new(&LanguageServiceParams {
+            lint_config: LintConfig(HashMap::from([(Lint::UnusedBinding, LintLevel::Deny)])),
+            ..Default::default()
+        });
+        let diagnostics = lang_services.do_validate(&text_document, false, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_word_specs_hover() {
+        let text_document = TextDocumentItem {
+            uri: Url::parse("file:///example.rain").unwrap(),
+            text: "---\n#main\n_: read<1 2>();".to_owned(),
+            version: 0,
+            language_id: "rainlang".to_owned(),
+        };
+        let operand_arg = |name: &str| OperandArgSpec {
+            name: name.to_owned(),
+            description: format!("the {} to read", name),
+            kind: OperandArgKind::Literal,
+            min: None,
+            max: None,
+            optional: name == "offset",
+        };
+        let lang_services = RainLanguageServices::new(&LanguageServiceParams {
+            word_specs: WordSpecs(HashMap::from([(
+                "read".to_owned(),
+                WordSpec {
+                    inputs: 0,
+                    max_inputs: None,
+                    outputs: 1,
+                    operand_args: Some(vec![operand_arg("index"), operand_arg("offset")]),
+                },
+            )])),
+            ..Default::default()
+        });
+        let hover_value = |character: u32| {
+            let hover = lang_services
+                .do_hover(&text_document, Position::new(2, character), None, None)
+                .unwrap();
+            match hover.contents {
+                lsp_types::HoverContents::Markup(v) => v.value,
+                _ => panic!("expected markup hover"),
+            }
+        };
+        assert_eq!(hover_value(4), "read<index offset?>\ninputs: 0, outputs: 1");
+        assert_eq!(hover_value(10), "offset\nthe offset to read");
+    }
+
+    #[test]
+    fn test_import_origin_related_information() {
+        let diagnostics = |import_uri: &str| {
+            let mut store = Store::new();
+            let (import_hash, _) = store
+                .set_dotrain("---\n#exp 1\n#exp 2", import_uri, false)
+                .unwrap();
+            let text_document = TextDocumentItem {
+                uri: Url::parse("file:///example.rain").unwrap(),
+                text: format!(
+                    "---\n@imp {}\n#main\n_: 1;",
+                    alloy_primitives::hex::encode_prefixed(import_hash)
+                ),
+                version: 0,
+                language_id: "rainlang".to_owned(),
+            };
+            let lang_services = RainLanguageServices::new(&LanguageServiceParams {
+                meta_store: Some(Arc::new(RwLock::new(store))),
+                ..Default::default()
+            });
+            lang_services.do_validate(&text_document, true, None)
+        };
+
+        let result = diagnostics("file:///import.rain");
+        assert_eq!(result.len(), 1);
+        let info = result[0].related_information.as_ref().unwrap();
+        assert_eq!(info.len(), 2);
+        assert_eq!(info[1].location.uri.as_str(), "file:///import.rain");
+
+        // an origin without a valid uri is not linked to the root document
+        let result = diagnostics("import.rain");
+        assert_eq!(result.len(), 1);
+        let info = result[0].related_information.as_ref().unwrap();
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].location.uri.as_str(), "file:///example.rain");
+    }
 }
@@ -0,0 +1,186 @@
+use dotrain::types::ast::Offsets;
+use lsp_types::{Position, PositionEncodingKind, Range};
+
+/// Encoding of the characters of LSP positions, i.e. the unit of [Position::character], the
+/// LSP default is UTF-16
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionEncoding {
+    /// characters are counted in bytes
+    Utf8,
+    /// characters are counted in UTF-16 code units
+    #[default]
+    Utf16,
+    /// characters are counted in unicode scalar values
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// The LSP position encoding kind of this encoding
+    pub fn kind(&self) -> PositionEncodingKind {
+        match self {
+            PositionEncoding::Utf8 => PositionEncodingKind::UTF8,
+            PositionEncoding::Utf16 => PositionEncodingKind::UTF16,
+            PositionEncoding::Utf32 => PositionEncodingKind::UTF32,
+        }
+    }
+
+    /// Creates from the given LSP position encoding kind, None if the kind is unknown
+    pub fn from_kind(kind: &PositionEncodingKind) -> Option<PositionEncoding> {
+        [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ]
+        .into_iter()
+        .find(|v| v.kind() == *kind)
+    }
+
+    /// Negotiates the position encoding from the encodings the client supports (`general.positionEncodings`
+    /// client capability), UTF-8 is preferred as it needs no conversion from byte offsets, otherwise the
+    /// first supported one the client provided is chosen, falls back to UTF-16 which all clients support
+    pub fn negotiate(client_encodings: Option<&[PositionEncodingKind]>) -> PositionEncoding {
+        let encodings = client_encodings
+            .unwrap_or_default()
+            .iter()
+            .filter_map(PositionEncoding::from_kind)
+            .collect::<Vec<_>>();
+        if encodings.contains(&PositionEncoding::Utf8) {
+            PositionEncoding::Utf8
+        } else {
+            encodings.first().copied().unwrap_or_default()
+        }
+    }
+
+    /// Length of the given char in this encoding's units
+    fn char_len(&self, c: char) -> usize {
+        match self {
+            PositionEncoding::Utf8 => c.len_utf8(),
+            PositionEncoding::Utf16 => c.len_utf16(),
+            PositionEncoding::Utf32 => 1,
+        }
+    }
+}
+
+/// Line index of a text that converts the byte offsets of RainDocument/Rainlang items to LSP
+/// positions and back in a position encoding, built once and shared by all conversions of a text
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_offsets: Vec<usize>,
+    encoding: PositionEncoding,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Builds the line index of the given text
+    pub fn new(text: &'a str, encoding: PositionEncoding) -> LineIndex<'a> {
+        let mut line_offsets = vec![0];
+        line_offsets.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        LineIndex {
+            text,
+            line_offsets,
+            encoding,
+        }
+    }
+
+    /// The position encoding of this line index
+    pub fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
+
+    /// Converts the given byte offset to a position, an offset inside of a char is floored to
+    /// the start of that char
+    pub fn position_at(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_offsets.partition_point(|v| *v <= offset) - 1;
+        let line_offset = self.line_offsets[line];
+        Position {
+            line: line as u32,
+            character: self.text[line_offset..offset]
+                .chars()
+                .map(|c| self.encoding.char_len(c))
+                .sum::<usize>() as u32,
+        }
+    }
+
+    /// Converts the given position to a byte offset, a character out of the line length
+    /// defaults back to the line length and a line out of range to the end of the text
+    pub fn offset_at(&self, position: &Position) -> usize {
+        let Some(line_offset) = self.line_offsets.get(position.line as usize) else {
+            return self.text.len();
+        };
+        let line = self.text[*line_offset..]
+            .split_inclusive('\n')
+            .next()
+            .unwrap_or_default();
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let mut character = 0;
+        for (i, c) in line.char_indices() {
+            if character >= position.character as usize {
+                return line_offset + i;
+            }
+            character += self.encoding.char_len(c);
+        }
+        line_offset + line.len()
+    }
+
+    /// Converts the given byte offsets to a range
+    pub fn range_at(&self, offsets: Offsets) -> Range {
+        Range::new(self.position_at(offsets[0]), self.position_at(offsets[1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_index() {
+        // "é" is 2 bytes, 1 UTF-16 code unit and 1 char, "𝄞" is 4 bytes, 2 UTF-16
+        // code units and 1 char
+        let text = "---\n/* é𝄞 */ #a 1\n#b 2";
+        let offset = text.find("#a").unwrap();
+        for (encoding, character) in [
+            (PositionEncoding::Utf8, 13),
+            (PositionEncoding::Utf16, 10),
+            (PositionEncoding::Utf32, 9),
+        ] {
+            let line_index = LineIndex::new(text, encoding);
+            let position = line_index.position_at(offset);
+            assert_eq!(position, Position::new(1, character));
+            assert_eq!(line_index.offset_at(&position), offset);
+        }
+
+        let line_index = LineIndex::new(text, PositionEncoding::Utf16);
+        assert_eq!(line_index.position_at(8), Position::new(1, 3));
+        assert_eq!(line_index.position_at(22), Position::new(2, 0));
+        assert_eq!(line_index.position_at(100), Position::new(2, 4));
+        assert_eq!(line_index.offset_at(&Position::new(1, 100)), 21);
+        assert_eq!(line_index.offset_at(&Position::new(5, 0)), text.len());
+        assert_eq!(
+            line_index.range_at([22, 24]),
+            Range::new(Position::new(2, 0), Position::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn test_negotiate_position_encoding() {
+        assert_eq!(PositionEncoding::negotiate(None), PositionEncoding::Utf16);
+        assert_eq!(
+            PositionEncoding::negotiate(Some(&[
+                PositionEncodingKind::UTF32,
+                PositionEncodingKind::UTF8
+            ])),
+            PositionEncoding::Utf8
+        );
+        assert_eq!(
+            PositionEncoding::negotiate(Some(&[
+                PositionEncodingKind::new("utf-7"),
+                PositionEncodingKind::UTF32
+            ])),
+            PositionEncoding::Utf32
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::collections::BTreeSet;
-use super::{OffsetAt, PositionAt};
+use super::{LineIndex, PositionEncoding};
 use lsp_types::{SemanticTokensPartialResult, Position, SemanticToken};
 use dotrain::{error::ErrorCode, types::ast::BindingItem, RainDocument};
 
@@ -15,7 +15,9 @@ pub fn get_semantic_token(
     rain_document: &RainDocument,
     token_types_index: u32,
     token_modifiers_len: usize,
+    position_encoding: PositionEncoding,
 ) -> SemanticTokensPartialResult {
+    let line_index = LineIndex::new(rain_document.text(), position_encoding);
     let mut ranges: BTreeSet<OrdRange> = BTreeSet::new();
     for binding in rain_document.bindings() {
         match &binding.item {
@@ -23,37 +25,29 @@ pub fn get_semantic_token(
                 binding.problems.iter().for_each(|p| {
                     if p.code == ErrorCode::ElidedBinding {
                         ranges.insert(OrdRange {
-                            start: rain_document.text().position_at(p.position[0]),
-                            end: rain_document.text().position_at(p.position[1]),
+                            start: line_index.position_at(p.position[0]),
+                            end: line_index.position_at(p.position[1]),
                         });
                     }
                 });
             }
             BindingItem::Elided(_) => {
-                let start = rain_document
-                    .text()
-                    .position_at(binding.content_position[0] + 1);
-                let end = rain_document
-                    .text()
-                    .position_at(binding.content_position[1]);
+                let start = line_index.position_at(binding.content_position[0] + 1);
+                let end = line_index.position_at(binding.content_position[1]);
                 if start.line == end.line {
                     ranges.insert(OrdRange { start, end });
                 } else {
                     ranges.insert(OrdRange {
                         start,
-                        end: rain_document.text().position_at(
-                            rain_document
-                                .text()
-                                .offset_at(&Position::new(start.line + 1, 0))
-                                - 1,
+                        end: line_index.position_at(
+                            line_index.offset_at(&Position::new(start.line + 1, 0)) - 1,
                         ),
                     });
                     for i in start.line + 1..end.line {
                         ranges.insert(OrdRange {
                             start: Position::new(i, 0),
-                            end: rain_document.text().position_at(
-                                rain_document.text().offset_at(&Position::new(i + 1, 0)) - 1,
-                            ),
+                            end: line_index
+                                .position_at(line_index.offset_at(&Position::new(i + 1, 0)) - 1),
                         });
                     }
                     ranges.insert(OrdRange {